@@ -1,5 +1,5 @@
 use itertools::Itertools;
-use num_traits::{Num, Signed};
+use num_traits::{CheckedAdd, Num, Signed};
 use std::collections::HashMap;
 use std::hash::Hash;
 use std::ops::{self, Index, IndexMut};
@@ -512,6 +512,87 @@ impl<T: Scalar, const N: usize> PointRange<T, N> {
     pub fn intersects(&self, other: &Self) -> bool {
         !((0..N).any(|i| self.min.0[i] >= other.max.0[i] || other.min.0[i] >= self.max.0[i]))
     }
+
+    pub fn is_empty(&self) -> bool {
+        (0..N).any(|i| self.min.0[i] >= self.max.0[i])
+    }
+
+    /// Number of points inside of the range.
+    pub fn volume(&self) -> T {
+        if self.is_empty() {
+            return T::zero();
+        }
+        (0..N).fold(T::one(), |acc, i| acc * (self.max.0[i] - self.min.0[i]))
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let intersection = Self {
+            min: self.min.max_componentwise(other.min),
+            max: self.max.min_componentwise(other.max),
+        };
+        (!intersection.is_empty()).then_some(intersection)
+    }
+
+    /// Cuts the range at `value` in `dimension`. The lower part keeps everything below `value`,
+    /// the upper part everything from `value` on. Empty parts are returned as `None`.
+    pub fn split_at(&self, dimension: usize, value: T) -> (Option<Self>, Option<Self>) {
+        let mut lower = *self;
+        let mut upper = *self;
+        lower.max.0[dimension] = lower.max.0[dimension].min(value);
+        upper.min.0[dimension] = upper.min.0[dimension].max(value);
+        (
+            (!lower.is_empty()).then_some(lower),
+            (!upper.is_empty()).then_some(upper),
+        )
+    }
+
+    /// Returns disjoint ranges covering exactly the points of `self` that are not in `other`.
+    pub fn subtract(&self, other: &Self) -> Vec<Self> {
+        if self.is_empty() {
+            return vec![];
+        }
+        let Some(intersection) = self.intersection(other) else {
+            return vec![*self];
+        };
+
+        let mut remainders = vec![];
+        let mut rest = *self;
+        for i in 0..N {
+            let (below, rest_and_above) = rest.split_at(i, intersection.min.0[i]);
+            remainders.extend(below);
+
+            let (inside, above) = rest_and_above.unwrap().split_at(i, intersection.max.0[i]);
+            remainders.extend(above);
+
+            rest = inside.unwrap();
+        }
+        remainders
+    }
+
+    /// Grows the range by `amount` in every direction.
+    pub fn expand(&self, amount: T) -> Self {
+        Self {
+            min: self.min - Point::filled(amount),
+            max: self.max + Point::filled(amount),
+        }
+    }
+
+    /// Shrinks the range by `amount` in every direction. Might leave an empty range.
+    pub fn shrink(&self, amount: T) -> Self {
+        Self {
+            min: self.min + Point::filled(amount),
+            max: self.max - Point::filled(amount),
+        }
+    }
+
+    /// Iterates over all points inside of the range.
+    pub fn points(&self) -> PointGridIterator<T, N> {
+        if self.is_empty() {
+            PointGridIterator::new(self.min, self.min)
+        } else {
+            PointGridIterator::new(self.min, self.max)
+        }
+    }
 }
 
 impl<T: Scalar, const N: usize> ops::Add<Point<T, N>> for PointRange<T, N> {
@@ -542,6 +623,173 @@ impl<T: Scalar, const N: usize> ops::Sub<Point<T, N>> for PointRange<T, N> {
     }
 }
 
+impl<T: Scalar> From<ops::Range<T>> for PointRange<T, 1> {
+    fn from(value: ops::Range<T>) -> Self {
+        Self {
+            min: Point([value.start]),
+            max: Point([value.end]),
+        }
+    }
+}
+
+/// Panics if the range ends at `T::MAX`, since the exclusive upper bound can't go past it.
+impl<T: Scalar + CheckedAdd> From<ops::RangeInclusive<T>> for PointRange<T, 1> {
+    fn from(value: ops::RangeInclusive<T>) -> Self {
+        let (start, end) = value.into_inner();
+        let max = end
+            .checked_add(&T::one())
+            .expect("inclusive range ending at T::MAX has no exclusive upper bound");
+        Self {
+            min: Point([start]),
+            max: Point([max]),
+        }
+    }
+}
+
+impl<T: Scalar> From<PointRange<T, 1>> for ops::Range<T> {
+    fn from(value: PointRange<T, 1>) -> Self {
+        value.min.0[0]..value.max.0[0]
+    }
+}
+
+/// A set of values stored as sorted, disjoint and non-touching 1D `PointRange`s. Like all
+/// `PointRange`s, the upper bound of every interval is exclusive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntervalSet<T: Scalar>(Vec<PointRange<T, 1>>);
+
+impl<T: Scalar> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self(vec![])
+    }
+}
+
+impl<T: Scalar> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds all values of `range`, merging it with overlapping or touching intervals.
+    pub fn insert(&mut self, range: impl Into<PointRange<T, 1>>) {
+        let range = range.into();
+        let (start, end) = (range.min.0[0], range.max.0[0]);
+        if start >= end {
+            return;
+        }
+
+        let i = self.0.partition_point(|r| r.max.0[0] < start);
+        let j = self.0.partition_point(|r| r.min.0[0] <= end);
+
+        let mut merged = PointRange::from(start..end);
+        if i < j {
+            merged.min.0[0] = merged.min.0[0].min(self.0[i].min.0[0]);
+            merged.max.0[0] = merged.max.0[0].max(self.0[j - 1].max.0[0]);
+        }
+        self.0.splice(i..j, [merged]);
+    }
+
+    /// Removes all values of `range`, splitting intervals where necessary.
+    pub fn remove(&mut self, range: impl Into<PointRange<T, 1>>) {
+        let range = range.into();
+        let (start, end) = (range.min.0[0], range.max.0[0]);
+        if start >= end {
+            return;
+        }
+
+        let i = self.0.partition_point(|r| r.max.0[0] <= start);
+        let j = self.0.partition_point(|r| r.min.0[0] < end);
+        if i >= j {
+            return;
+        }
+
+        let mut remainders = vec![];
+        if self.0[i].min.0[0] < start {
+            remainders.push(PointRange::from(self.0[i].min.0[0]..start));
+        }
+        if self.0[j - 1].max.0[0] > end {
+            remainders.push(PointRange::from(end..self.0[j - 1].max.0[0]));
+        }
+        self.0.splice(i..j, remainders);
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.0.partition_point(|r| r.max.0[0] <= value);
+        i < self.0.len() && self.0[i].min.0[0] <= value
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for r in &other.0 {
+            result.insert(*r);
+        }
+        result
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut result = vec![];
+        let (mut i, mut j) = (0, 0);
+        while i < self.0.len() && j < other.0.len() {
+            let (a, b) = (self.0[i], other.0[j]);
+            let start = a.min.0[0].max(b.min.0[0]);
+            let end = a.max.0[0].min(b.max.0[0]);
+            if start < end {
+                result.push(PointRange::from(start..end));
+            }
+            if a.max.0[0] < b.max.0[0] {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self(result)
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for r in &other.0 {
+            result.remove(*r);
+        }
+        result
+    }
+
+    /// Number of values covered by all intervals together.
+    pub fn total_length(&self) -> T {
+        self.0
+            .iter()
+            .fold(T::zero(), |acc, r| acc + r.max.0[0] - r.min.0[0])
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Number of disjoint intervals.
+    pub fn interval_count(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn iter(&self) -> Iter<'_, PointRange<T, 1>> {
+        self.0.iter()
+    }
+
+    pub fn ranges(&self) -> impl Iterator<Item = ops::Range<T>> {
+        self.0.iter().map(|r| ops::Range::from(*r))
+    }
+
+    pub fn ranges_inclusive(&self) -> impl Iterator<Item = ops::RangeInclusive<T>> {
+        self.0.iter().map(|r| r.min.0[0]..=(r.max.0[0] - T::one()))
+    }
+}
+
+impl<T: Scalar, R: Into<PointRange<T, 1>>> FromIterator<R> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = R>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for r in iter {
+            set.insert(r);
+        }
+        set
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PointGrid<T: Scalar, const N: usize, U>(pub HashMap<Point<T, N>, U>);
 
@@ -594,6 +842,88 @@ impl<T: Scalar + Ord, const N: usize, U> PointGrid<T, N, U> {
     }
 }
 
+/// Error returned by `PointGrid::parse` for characters that neither map to a value nor are marked
+/// as empty or special. `line` and `column` are 1-based, like in an editor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridParseError {
+    pub line: usize,
+    pub column: usize,
+    pub character: char,
+}
+
+impl fmt::Display for GridParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unrecognised character {:?} at line {}, column {}",
+            self.character, self.line, self.column
+        )
+    }
+}
+
+impl std::error::Error for GridParseError {}
+
+/// Positions of the special characters found by `PointGrid::parse`, in reading order.
+pub type SpecialPositions = HashMap<char, Vec<Point2<isize>>>;
+
+impl<U> PointGrid<isize, 2, U> {
+    /// Parses puzzle text into a grid with `x` as column and `y` as line index.
+    ///
+    /// Characters in `empty` are skipped. Positions of characters in `special` are collected
+    /// into the returned map and are only stored in the grid if `mapping` accepts them. Any other
+    /// character that `mapping` returns `None` for is an error.
+    pub fn parse(
+        input: &str,
+        empty: &[char],
+        special: &[char],
+        mapping: impl Fn(char) -> Option<U>,
+    ) -> Result<(Self, SpecialPositions), GridParseError> {
+        let mut grid = PointGrid::default();
+        let mut specials = SpecialPositions::new();
+
+        for (y, row) in input.trim_end_matches(['\n', '\r']).lines().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                let p = Point2::new(x as isize, y as isize);
+
+                if empty.contains(&c) {
+                    continue;
+                }
+
+                let is_special = special.contains(&c);
+                if is_special {
+                    specials.entry(c).or_default().push(p);
+                }
+
+                match mapping(c) {
+                    Some(u) => grid.insert(p, u),
+                    None if is_special => {}
+                    None => {
+                        return Err(GridParseError {
+                            line: y + 1,
+                            column: x + 1,
+                            character: c,
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok((grid, specials))
+    }
+
+    /// Same as `parse`, using the `TryFrom<char>` implementation of `U` as mapping.
+    pub fn parse_tiles(
+        input: &str,
+        empty: &[char],
+        special: &[char],
+    ) -> Result<(Self, SpecialPositions), GridParseError>
+    where
+        U: TryFrom<char>,
+    {
+        Self::parse(input, empty, special, |c| U::try_from(c).ok())
+    }
+}
+
 impl<T: Scalar + Ord + std::iter::Step + std::hash::Hash, U: fmt::Display> fmt::Display
     for PointGrid<T, 2, U>
 {
@@ -698,9 +1028,232 @@ impl<T: Scalar + PartialOrd, const N: usize> Iterator for PointGridIterator<T, N
     }
 }
 
+/// Grid over fixed bounds, stored in a flat `Vec` with the first dimension varying fastest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DenseGrid<U, const N: usize> {
+    bounds: PointRange<isize, N>,
+    cells: Vec<U>,
+}
+
+impl<U, const N: usize> DenseGrid<U, N> {
+    pub fn new(bounds: PointRange<isize, N>, fill: U) -> Self
+    where
+        U: Clone,
+    {
+        let len = Self::len_of(&bounds);
+        Self {
+            bounds,
+            cells: vec![fill; len],
+        }
+    }
+
+    pub fn from_fn(bounds: PointRange<isize, N>, mut f: impl FnMut(Point<isize, N>) -> U) -> Self {
+        let len = Self::len_of(&bounds);
+        let mut grid = Self {
+            bounds,
+            cells: Vec::with_capacity(len),
+        };
+        for i in 0..len {
+            let p = grid.point_of(i).unwrap();
+            grid.cells.push(f(p));
+        }
+        grid
+    }
+
+    pub fn from_point_grid(grid: PointGrid<isize, N, U>, fill: U) -> Self
+    where
+        U: Clone,
+    {
+        if grid.0.is_empty() {
+            return Self::new(PointRange::new(Point::zero(), Point::zero()), fill);
+        }
+        let mut dense = Self::new(grid.dimensions_as_range(), fill);
+        for (p, u) in grid.0 {
+            dense.set(&p, u);
+        }
+        dense
+    }
+
+    pub fn to_point_grid(&self, keep: impl Fn(&U) -> bool) -> PointGrid<isize, N, U>
+    where
+        U: Clone,
+    {
+        PointGrid(
+            self.iter()
+                .filter(|(_, u)| keep(u))
+                .map(|(p, u)| (p, u.clone()))
+                .collect(),
+        )
+    }
+
+    fn len_of(bounds: &PointRange<isize, N>) -> usize {
+        (0..N)
+            .map(|d| (bounds.max.0[d] - bounds.min.0[d]).max(0) as usize)
+            .product()
+    }
+
+    pub fn bounds(&self) -> PointRange<isize, N> {
+        self.bounds
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, p: &Point<isize, N>) -> bool {
+        self.bounds.contains(p)
+    }
+
+    pub fn index_of(&self, p: &Point<isize, N>) -> Option<usize> {
+        if !self.contains(p) {
+            return None;
+        }
+
+        let mut index = 0;
+        let mut stride = 1;
+        for d in 0..N {
+            index += (p.0[d] - self.bounds.min.0[d]) as usize * stride;
+            stride *= (self.bounds.max.0[d] - self.bounds.min.0[d]) as usize;
+        }
+        Some(index)
+    }
+
+    pub fn point_of(&self, index: usize) -> Option<Point<isize, N>> {
+        if index >= Self::len_of(&self.bounds) {
+            return None;
+        }
+
+        Some(dense_point_of(&self.bounds, index))
+    }
+
+    pub fn get(&self, p: &Point<isize, N>) -> Option<&U> {
+        self.index_of(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, p: &Point<isize, N>) -> Option<&mut U> {
+        self.index_of(p).map(|i| &mut self.cells[i])
+    }
+
+    pub fn set(&mut self, p: &Point<isize, N>, value: U) -> Option<U> {
+        self.get_mut(p).map(|u| std::mem::replace(u, value))
+    }
+
+    pub fn values(&self) -> Iter<'_, U> {
+        self.cells.iter()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point<isize, N>, &U)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, u)| (self.point_of(i).unwrap(), u))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Point<isize, N>, &mut U)> {
+        let bounds = self.bounds;
+        self.cells
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, u)| (dense_point_of(&bounds, i), u))
+    }
+
+    pub fn slice(
+        &self,
+        dimension: usize,
+        value: isize,
+    ) -> impl Iterator<Item = (Point<isize, N>, &U)> {
+        let mut plane = self.bounds;
+        plane.min.0[dimension] = value;
+        plane.max.0[dimension] = value + 1;
+
+        plane
+            .intersection(&self.bounds)
+            .into_iter()
+            .flat_map(|r| r.points())
+            .map(|p| (p, self.get(&p).unwrap()))
+    }
+}
+
+fn dense_point_of<const N: usize>(
+    bounds: &PointRange<isize, N>,
+    mut index: usize,
+) -> Point<isize, N> {
+    let mut p = bounds.min;
+    for d in 0..N {
+        let extent = (bounds.max.0[d] - bounds.min.0[d]) as usize;
+        p.0[d] += (index % extent) as isize;
+        index /= extent;
+    }
+    p
+}
+
+impl<U> DenseGrid<U, 2> {
+    pub fn width(&self) -> usize {
+        (self.bounds.max.0[0] - self.bounds.min.0[0]).max(0) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.bounds.max.0[1] - self.bounds.min.0[1]).max(0) as usize
+    }
+
+    pub fn row(&self, y: isize) -> Option<&[U]> {
+        let start = self.index_of(&Point2::new(self.bounds.min.0[0], y))?;
+        Some(&self.cells[start..start + self.width()])
+    }
+
+    pub fn column(&self, x: isize) -> impl Iterator<Item = &U> {
+        let start = self.index_of(&Point2::new(x, self.bounds.min.0[1]));
+        let width = self.width();
+        start
+            .into_iter()
+            .flat_map(move |start| self.cells[start..].iter().step_by(width))
+    }
+}
+
+impl<U: Clone + Default, const N: usize> From<PointGrid<isize, N, U>> for DenseGrid<U, N> {
+    fn from(value: PointGrid<isize, N, U>) -> Self {
+        Self::from_point_grid(value, U::default())
+    }
+}
+
+impl<U, const N: usize> From<DenseGrid<U, N>> for PointGrid<isize, N, U> {
+    fn from(value: DenseGrid<U, N>) -> Self {
+        let bounds = value.bounds;
+        let mut grid = PointGrid::default();
+        for (i, u) in value.cells.into_iter().enumerate() {
+            grid.insert(dense_point_of(&bounds, i), u);
+        }
+        grid
+    }
+}
+
+impl<U: fmt::Display, const N: usize> fmt::Display for DenseGrid<U, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = (self.bounds.max.0[0] - self.bounds.min.0[0]).max(1) as usize;
+        writeln!(
+            f,
+            "Grid ({}, {}):",
+            self.bounds.min,
+            self.bounds.max - Point::one()
+        )?;
+        for (i, u) in self.cells.iter().enumerate() {
+            write!(f, "{}", u)?;
+            if (i + 1) % width == 0 {
+                writeln!(f)?;
+            }
+        }
+        write!(f, "")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashSet;
 
     #[test]
     fn test_directions() {
@@ -834,4 +1387,170 @@ mod tests {
         assert!(cube.contains(&Point3::new(1, 2, 1)));
         assert!(!cube.contains(&Point3::new(1, 3, 1)));
     }
+
+    #[test]
+    fn test_point_range_algebra() {
+        let rect1: Rectangle<isize> = Rectangle::new(Point2::new(0, 0), Point2::new(10, 10));
+        let rect2: Rectangle<isize> = Rectangle::new(Point2::new(5, 5), Point2::new(15, 15));
+        let rect3: Rectangle<isize> = Rectangle::new(Point2::new(10, 10), Point2::new(20, 20));
+
+        assert_eq!(rect1.volume(), 100);
+        assert_eq!(
+            rect1.intersection(&rect2),
+            Some(Rectangle::new(Point2::new(5, 5), Point2::new(10, 10)))
+        );
+        assert_eq!(rect1.intersection(&rect3), None);
+        assert!(rect1.shrink(5).is_empty());
+        assert_eq!(rect1.shrink(5).volume(), 0);
+        assert_eq!(rect1.expand(1).volume(), 144);
+
+        let (lower, upper) = rect1.split_at(1, 3);
+        assert_eq!(lower.map(|r| r.volume()), Some(30));
+        assert_eq!(upper.map(|r| r.volume()), Some(70));
+        assert_eq!(rect1.split_at(0, 20), (Some(rect1), None));
+
+        let remainders = rect1.subtract(&rect2);
+        assert_eq!(remainders.iter().map(|r| r.volume()).sum::<isize>(), 75);
+        assert!(
+            remainders
+                .iter()
+                .tuple_combinations()
+                .all(|(a, b)| !a.intersects(b))
+        );
+        assert!(remainders.iter().all(|r| !r.intersects(&rect2)));
+        assert_eq!(rect1.subtract(&rect3), vec![rect1]);
+        assert!(rect2.subtract(&rect2.expand(1)).is_empty());
+
+        let cube: Cube<isize> = Cube::new(Point3::new(0, 0, 0), Point3::new(3, 3, 3));
+        let hole: Cube<isize> = Cube::new(Point3::new(1, 1, 1), Point3::new(2, 2, 2));
+        let shell = cube.subtract(&hole);
+        assert_eq!(shell.len(), 6);
+        assert_eq!(shell.iter().map(|c| c.volume()).sum::<isize>(), 26);
+        assert_eq!(
+            shell
+                .iter()
+                .flat_map(|c| c.points())
+                .collect::<HashSet<_>>()
+                .len(),
+            26
+        );
+        assert_eq!(hole.points().collect_vec(), vec![Point3::new(1, 1, 1)]);
+        assert_eq!(hole.shrink(1).points().count(), 0);
+    }
+
+    #[test]
+    fn test_interval_set_insert_and_remove() {
+        let mut set: IntervalSet<u64> = [3..=5, 10..=14, 16..=20, 12..=18].into_iter().collect();
+        assert_eq!(set.ranges_inclusive().collect_vec(), vec![3..=5, 10..=20]);
+        assert_eq!(set.total_length(), 14);
+
+        set.insert(6..10);
+        assert_eq!(set.ranges().collect_vec(), vec![3..21]);
+
+        set.remove(8..=9);
+        set.remove(0..4);
+        assert_eq!(set.ranges().collect_vec(), vec![4..8, 10..21]);
+        assert!(set.contains(4));
+        assert!(!set.contains(8));
+        assert!(set.contains(20));
+        assert!(!set.contains(21));
+        assert_eq!(set.interval_count(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "no exclusive upper bound")]
+    fn test_interval_set_up_to_max() {
+        let set: IntervalSet<u8> = [250..=u8::MAX - 1].into_iter().collect();
+        assert_eq!(set.ranges().collect_vec(), vec![250..u8::MAX]);
+
+        let _: IntervalSet<u8> = [250..=u8::MAX].into_iter().collect();
+    }
+
+    #[test]
+    fn test_interval_set_operations() {
+        let a: IntervalSet<i32> = [0..10, 20..30].into_iter().collect();
+        let mut b: IntervalSet<i32> = IntervalSet::new();
+        b.insert(5..25);
+
+        assert_eq!(a.union(&b).ranges().collect_vec(), vec![0..30]);
+        assert_eq!(
+            a.intersection(&b).ranges().collect_vec(),
+            vec![5..10, 20..25]
+        );
+        assert_eq!(a.difference(&b).ranges().collect_vec(), vec![0..5, 25..30]);
+        assert_eq!(b.difference(&a).ranges().collect_vec(), vec![10..20]);
+        assert!(a.difference(&a).is_empty());
+    }
+
+    #[test]
+    fn test_point_grid_parse() {
+        let input = "S.#\n#.E\n\n";
+        let (grid, specials) = PointGrid::parse(input, &['.'], &['S', 'E'], |c| match c {
+            '#' => Some(true),
+            'E' => Some(false),
+            _ => None,
+        })
+        .unwrap();
+
+        assert_eq!(grid.0.len(), 3);
+        assert_eq!(grid.get(&Point2::new(2, 0)), Some(&true));
+        assert_eq!(grid.get(&Point2::new(0, 1)), Some(&true));
+        assert_eq!(grid.get(&Point2::new(2, 1)), Some(&false));
+        assert_eq!(grid.get(&Point2::new(0, 0)), None);
+        assert_eq!(specials[&'S'], vec![Point2::new(0, 0)]);
+        assert_eq!(specials[&'E'], vec![Point2::new(2, 1)]);
+
+        let err = PointGrid::<isize, 2, ()>::parse("..\n.x", &['.'], &[], |_| None).unwrap_err();
+        assert_eq!(
+            err,
+            GridParseError {
+                line: 2,
+                column: 2,
+                character: 'x'
+            }
+        );
+    }
+
+    #[test]
+    fn test_dense_grid_indexing() {
+        let mut grid: DenseGrid<u8, 2> =
+            DenseGrid::new(Rectangle::new(Point2::new(-1, -2), Point2::new(2, 1)), 0);
+        assert_eq!(grid.len(), 9);
+        assert_eq!(grid.index_of(&Point2::new(-1, -2)), Some(0));
+        assert_eq!(grid.index_of(&Point2::new(0, -2)), Some(1));
+        assert_eq!(grid.index_of(&Point2::new(-1, -1)), Some(3));
+        assert_eq!(grid.index_of(&Point2::new(2, 0)), None);
+        assert_eq!(grid.point_of(5), Some(Point2::new(1, -1)));
+
+        assert_eq!(grid.set(&Point2::new(1, 0), 7), Some(0));
+        assert_eq!(grid.set(&Point2::new(5, 5), 7), None);
+        assert_eq!(grid.get(&Point2::new(1, 0)), Some(&7));
+        *grid.get_mut(&Point2::new(-1, 0)).unwrap() = 3;
+
+        assert_eq!(grid.row(0), Some(&[3, 0, 7][..]));
+        assert_eq!(grid.row(1), None);
+        assert_eq!(grid.column(1).copied().collect_vec(), vec![0, 0, 7]);
+        assert_eq!(
+            grid.slice(1, 0).map(|(_, u)| *u).collect_vec(),
+            vec![3, 0, 7]
+        );
+        assert_eq!(grid.slice(1, 4).count(), 0);
+    }
+
+    #[test]
+    fn test_dense_grid_point_grid_conversion() {
+        let mut pg: PointGrid<isize, 2, char> = PointGrid::default();
+        pg.insert(Point2::new(0, 0), '#');
+        pg.insert(Point2::new(2, 1), '#');
+        pg.insert(Point2::new(1, 2), '#');
+
+        let dense = DenseGrid::from_point_grid(pg.clone(), ' ');
+        assert_eq!(dense.len(), 9);
+        assert_eq!(dense.to_string(), pg.to_string());
+        assert_eq!(dense.to_point_grid(|c| *c == '#'), pg);
+
+        let full: PointGrid<isize, 2, char> = dense.into();
+        assert_eq!(full.0.len(), 9);
+        assert_eq!(full.get(&Point2::new(1, 1)), Some(&' '));
+    }
 }
@@ -1,17 +1,19 @@
 use std::fmt::{Display, Write};
 
-use advent_of_code::algebra_helpers::{Point2, Point2Direction, PointGrid};
+use advent_of_code::algebra_helpers::{DenseGrid, Point2, Point2Direction, PointGrid};
 
 advent_of_code::solution!(4);
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Tile {
+    Empty,
     PaperRoll,
 }
 
 impl Display for Tile {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            Self::Empty => f.write_char('.'),
             Self::PaperRoll => f.write_char('@'),
         }
     }
@@ -28,21 +30,12 @@ impl TryFrom<char> for Tile {
     }
 }
 
-fn generate_map(input: &str) -> PointGrid<isize, 2, Tile> {
-    let mut map = PointGrid::default();
-
-    for (y, row) in input.trim().lines().enumerate() {
-        for (x, c) in row.char_indices() {
-            if let Ok(tile) = c.try_into() {
-                map.insert(Point2::new(x as isize, y as isize), tile);
-            }
-        }
-    }
-
-    map
+fn generate_map(input: &str) -> Option<DenseGrid<Tile, 2>> {
+    let (map, _) = PointGrid::parse_tiles(input.trim(), &['.'], &[]).ok()?;
+    Some(DenseGrid::from_point_grid(map, Tile::Empty))
 }
 
-fn is_tile_accessible(map: &PointGrid<isize, 2, Tile>, position: &Point2<isize>) -> bool {
+fn is_tile_accessible(map: &DenseGrid<Tile, 2>, position: &Point2<isize>) -> bool {
     let mut count = 0;
     for d in Point2Direction::all_with_diagonals() {
         if let Some(Tile::PaperRoll) = map.get(&(position.get_point_in_direction(d, 1))) {
@@ -52,24 +45,25 @@ fn is_tile_accessible(map: &PointGrid<isize, 2, Tile>, position: &Point2<isize>)
     count < 4
 }
 
-fn remove_accessible_tiles(map: &PointGrid<isize, 2, Tile>) -> (PointGrid<isize, 2, Tile>, usize) {
-    let mut new_map = PointGrid::default();
+fn remove_accessible_tiles(map: &DenseGrid<Tile, 2>) -> (DenseGrid<Tile, 2>, usize) {
+    let mut new_map = map.clone();
+    let mut removed_elements = 0;
 
-    for p in map.0.keys() {
-        if !is_tile_accessible(map, p) {
-            new_map.insert(*p, Tile::PaperRoll);
+    for (p, tile) in map.iter() {
+        if *tile == Tile::PaperRoll && is_tile_accessible(map, &p) {
+            new_map.set(&p, Tile::Empty);
+            removed_elements += 1;
         }
     }
 
-    let removed_elements = map.0.len() - new_map.0.len();
     (new_map, removed_elements)
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let map = generate_map(input);
+    let map = generate_map(input)?;
     let mut result = 0;
-    for p in map.0.keys() {
-        if is_tile_accessible(&map, p) {
+    for (p, tile) in map.iter() {
+        if *tile == Tile::PaperRoll && is_tile_accessible(&map, &p) {
             result += 1;
         }
     }
@@ -77,7 +71,7 @@ pub fn part_one(input: &str) -> Option<u64> {
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let mut map = generate_map(input);
+    let mut map = generate_map(input)?;
     let mut result = 0;
     let mut current = 1;
 
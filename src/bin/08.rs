@@ -1,49 +1,19 @@
-#![feature(iter_map_windows)]
-use std::{collections::HashSet, hash::Hash};
-
-use advent_of_code::algebra_helpers::Point3;
-use itertools::Itertools;
-
-advent_of_code::solution!(8);
-
-#[derive(Debug, Clone, Copy)]
-struct Edge {
-    a: Point3<isize>,
-    b: Point3<isize>,
-}
-
-impl Edge {
-    fn distance(&self) -> u64 {
-        (self.a - self.b).length_euclid_squared() as u64
-    }
-
-    fn target(&self, source: Point3<isize>) -> Option<Point3<isize>> {
-        if source == self.a {
-            Some(self.b)
-        } else if source == self.b {
-            Some(self.a)
-        } else {
-            None
-        }
-    }
-}
-
-impl Eq for Edge {}
-impl PartialEq for Edge {
-    fn eq(&self, other: &Self) -> bool {
-        (self.a == other.a && self.b == other.b) || (self.a == other.b && self.b == other.a)
-    }
+use advent_of_code::{
+    algebra_helpers::Point3,
+    mst::{WeightedEdge, kruskal_presorted},
+    spatial::{KdTree, Metric},
+    union_find::UnionFind,
+};
+
+advent_of_code::solution!(8, parse = generate_map);
+
+pub struct Playground {
+    boxes: Vec<Point3<isize>>,
+    tree: KdTree<isize, 3>,
 }
 
-impl Hash for Edge {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.a.min_componentwise(self.b).hash(state);
-        self.a.max_componentwise(self.b).hash(state);
-    }
-}
-
-fn generate_map(input: &str) -> Vec<Point3<isize>> {
-    input
+pub fn generate_map(input: &str) -> Playground {
+    let boxes: Vec<Point3<isize>> = input
         .trim()
         .lines()
         .map(|l| {
@@ -54,103 +24,51 @@ fn generate_map(input: &str) -> Vec<Point3<isize>> {
                 parts.next().unwrap().parse().unwrap(),
             )
         })
-        .collect()
+        .collect();
+    let tree = KdTree::new(&boxes);
+
+    Playground { boxes, tree }
 }
 
-fn generate_edges(map: &[Point3<isize>]) -> Vec<Edge> {
-    let mut edges: HashSet<Edge> = HashSet::new();
+fn find_last_connecting_edge(map: &Playground) -> (Point3<isize>, Point3<isize>) {
+    let pairs = map
+        .tree
+        .pairs_by_distance(Metric::EuclidSquared)
+        .map(WeightedEdge::from);
 
-    for &a in map {
-        for &b in map {
-            if a == b {
-                continue;
-            }
-            edges.insert(Edge { a, b });
-        }
-    }
-    edges
-        .into_iter()
-        .sorted_by_key(|e| e.distance())
-        .collect::<Vec<Edge>>()
-}
+    let edge = kruskal_presorted(map.boxes.len(), pairs)
+        .last()
+        .unwrap()
+        .edge;
 
-fn find_group_sizes(map: &[Point3<isize>], edges: &[Edge]) -> Vec<u64> {
-    let mut visited_nodes = HashSet::new();
-    let mut group_sizes = vec![];
-
-    for starting_point in map {
-        if visited_nodes.contains(starting_point) {
-            continue;
-        }
-
-        let mut current_group_size = 0;
-        let mut stack = vec![*starting_point];
-        while let Some(current_node) = stack.pop() {
-            if visited_nodes.contains(&current_node) {
-                continue;
-            }
-
-            visited_nodes.insert(current_node);
-            current_group_size += 1;
-
-            for target in edges.iter().filter_map(|e| e.target(current_node)) {
-                if visited_nodes.contains(&target) {
-                    continue;
-                }
-                stack.push(target);
-            }
-        }
-        group_sizes.push(current_group_size);
-    }
-    group_sizes
+    (map.boxes[edge.a], map.boxes[edge.b])
 }
 
-fn binary_search(input: &str) -> Edge {
-    let map = generate_map(input);
-    let edges = generate_edges(&map);
-    let (mut min, mut max) = (0, edges.len() - 1);
-    while min <= max {
-        let cur = (max - min) / 2 + min;
-        let group_sizes = find_group_sizes(
-            &map,
-            &edges.iter().take(cur).cloned().collect::<Vec<Edge>>(),
-        );
-        if group_sizes.len() == 1 {
-            max = cur - 1;
-        } else {
-            min = cur + 1;
-        }
-    }
-    println!(
-        "Edges: {} {:?} {} {:?}",
-        min,
-        edges.get(min).unwrap(),
-        max,
-        edges.get(max).unwrap()
-    );
-
-    *edges.get(max).unwrap()
-}
+fn _part_one(map: &Playground, size: usize) -> Option<u64> {
+    let mut circuits = UnionFind::new(map.boxes.len());
 
-fn _part_one(input: &str, size: usize) -> Option<u64> {
-    let map = generate_map(input);
-    let edges = generate_edges(&map);
-    let subset_edges = edges.into_iter().take(size).collect::<Vec<Edge>>();
-    let mut group_sizes = find_group_sizes(&map, &subset_edges);
-    group_sizes.sort();
-    group_sizes.reverse();
+    for (a, b, _) in map.tree.pairs_by_distance(Metric::EuclidSquared).take(size) {
+        circuits.union(a, b);
+    }
 
-    Some(group_sizes.first().unwrap() * group_sizes.get(1).unwrap() * group_sizes.get(2).unwrap())
+    Some(
+        circuits
+            .component_sizes()
+            .iter()
+            .take(3)
+            .map(|&s| s as u64)
+            .product(),
+    )
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    _part_one(input, 1000)
+pub fn part_one(map: &Playground) -> Option<u64> {
+    _part_one(map, 1000)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let edge = binary_search(input);
+pub fn part_two(map: &Playground) -> Option<u64> {
+    let (a, b) = find_last_connecting_edge(map);
 
-    Some(edge.a.0[0] as u64 * edge.b.0[0] as u64)
+    Some(a.0[0] as u64 * b.0[0] as u64)
 }
 
 #[cfg(test)]
@@ -159,13 +77,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = _part_one(&advent_of_code::template::read_file("examples", DAY), 10);
+        let map = generate_map(&advent_of_code::template::read_file("examples", DAY));
+        let result = _part_one(&map, 10);
         assert_eq!(result, Some(40));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let map = generate_map(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&map);
         assert_eq!(result, Some(25272));
     }
 }
@@ -1,6 +1,9 @@
 #![feature(step_trait)]
-#![feature(iter_order_by)]
 pub mod algebra_helpers;
 pub mod helpers;
+pub mod mst;
+pub mod search;
+pub mod spatial;
 pub mod template;
+pub mod union_find;
 // Use this file to add helper functions and additional modules.
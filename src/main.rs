@@ -1,81 +1,145 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{all, download, read, scaffold, solve, time, verify};
 use args::{AppArguments, parse};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
+use advent_of_code::template::{Day, PuzzleId};
 #[cfg(feature = "today")]
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{Day, HistoryOptions, Limits, PuzzleId, Year, scoped_year};
+    use std::{process, time::Duration};
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: PuzzleId,
+            force: bool,
         },
         Read {
-            day: Day,
+            puzzle: PuzzleId,
+            force: bool,
         },
         Scaffold {
-            day: Day,
+            puzzle: PuzzleId,
             download: bool,
             overwrite: bool,
         },
         Solve {
-            day: Day,
+            puzzle: PuzzleId,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            force: bool,
+            build_once: bool,
         },
         All {
+            year: Option<Year>,
             release: bool,
+            jobs: usize,
+            build_once: bool,
+            limits: Limits,
         },
         Time {
+            year: Option<Year>,
             all: bool,
             day: Option<Day>,
             store: bool,
+            build_once: bool,
+            limits: Limits,
+            history: HistoryOptions,
+        },
+        Verify {
+            year: Option<Year>,
+            day: Option<Day>,
+            release: bool,
+            build_once: bool,
+            limits: Limits,
         },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            year: Option<Year>,
+        },
+    }
+
+    /// `--timeout <seconds>` and `--memory <megabytes>` for commands that run multiple days.
+    fn parse_limits(args: &mut pico_args::Arguments) -> Result<Limits, pico_args::Error> {
+        Ok(Limits {
+            timeout: args
+                .opt_value_from_str::<_, f64>("--timeout")?
+                .map(Duration::from_secs_f64),
+            memory_mb: args.opt_value_from_str("--memory")?,
+        })
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let subcommand = args.subcommand()?;
+        // every command accepts `--year`, the workspace's own event is used without it.
+        let year = scoped_year(args.opt_value_from_str("--year")?);
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
+                year,
                 release: args.contains("--release"),
+                jobs: args.opt_value_from_str(["-j", "--jobs"])?.unwrap_or(1),
+                build_once: args.contains("--build-once"),
+                limits: parse_limits(&mut args)?,
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let build_once = args.contains("--build-once");
+                let limits = parse_limits(&mut args)?;
+                let history = HistoryOptions {
+                    label: args.opt_value_from_str("--label")?,
+                    compare: args.contains("--compare"),
+                    baseline: args.opt_value_from_str("--baseline")?,
+                    threshold: args
+                        .opt_value_from_str("--threshold")?
+                        .unwrap_or(HistoryOptions::default().threshold),
+                };
 
                 AppArguments::Time {
+                    year,
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    build_once,
+                    limits,
+                    history,
                 }
             }
+            Some("verify") => AppArguments::Verify {
+                year,
+                release: args.contains("--release"),
+                build_once: args.contains("--build-once"),
+                limits: parse_limits(&mut args)?,
+                day: args.opt_free_from_str()?,
+            },
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                force: args.contains("--force"),
+                puzzle: PuzzleId::new(year, args.free_from_str()?),
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                force: args.contains("--force"),
+                puzzle: PuzzleId::new(year, args.free_from_str()?),
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                puzzle: PuzzleId::new(year, args.free_from_str()?),
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
             },
             Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+                puzzle: PuzzleId::new(year, args.free_from_str()?),
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
+                force: args.contains("--force"),
+                build_once: args.contains("--build-once"),
                 dhat: args.contains("--dhat"),
             },
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
+            Some("today") => AppArguments::Today { year },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -102,38 +166,63 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
+            AppArguments::All {
+                year,
+                release,
+                jobs,
+                build_once,
+                limits,
+            } => all::handle(year, release, jobs, build_once, limits),
+            AppArguments::Time {
+                year,
                 day,
+                all,
+                store,
+                build_once,
+                limits,
+                history,
+            } => time::handle(year, day, all, store, build_once, limits, history),
+            AppArguments::Verify {
+                year,
+                day,
+                release,
+                build_once,
+                limits,
+            } => verify::handle(year, day, release, build_once, limits),
+            AppArguments::Download { puzzle, force } => download::handle(puzzle, force),
+            AppArguments::Read { puzzle, force } => read::handle(puzzle, force),
+            AppArguments::Scaffold {
+                puzzle,
                 download,
                 overwrite,
             } => {
-                scaffold::handle(day, overwrite);
+                // download first, so that the examples and answers end up in the scaffold.
                 if download {
-                    download::handle(day);
+                    download::handle(puzzle, false);
                 }
+                scaffold::handle(puzzle, overwrite);
             }
             AppArguments::Solve {
-                day,
+                puzzle,
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                force,
+                build_once,
+            } => solve::handle(puzzle, release, dhat, submit, force, build_once),
             #[cfg(feature = "today")]
-            AppArguments::Today => {
+            AppArguments::Today { year } => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                        let puzzle = PuzzleId::new(year, day);
+                        download::handle(puzzle, false);
+                        scaffold::handle(puzzle, false);
+                        read::handle(puzzle, false)
                     }
                     None => {
                         eprintln!(
                             "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
+                            the last day of the event in december. Please use `scaffold` with a specific day."
                         );
                         process::exit(1)
                     }
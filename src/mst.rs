@@ -0,0 +1,314 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use itertools::Itertools;
+
+use crate::algebra_helpers::{Point, Scalar};
+use crate::union_find::UnionFind;
+
+/// An undirected edge between the nodes `a` and `b`, e.g. indices into a slice of points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WeightedEdge<C> {
+    pub weight: C,
+    pub a: usize,
+    pub b: usize,
+}
+
+impl<C> From<(usize, usize, C)> for WeightedEdge<C> {
+    fn from((a, b, weight): (usize, usize, C)) -> Self {
+        Self { weight, a, b }
+    }
+}
+
+/// A single accepted edge and the state of the forest right after adding it. The sizes of all
+/// components at this point are available from `Kruskal::component_sizes`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MstStep<C> {
+    pub edge: WeightedEdge<C>,
+    /// Size of the component the edge just merged into.
+    pub component_size: usize,
+    pub component_count: usize,
+}
+
+/// The accepted edges of a spanning tree (or forest, if the graph is not connected) in the order
+/// they were added.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpanningTree<C> {
+    pub node_count: usize,
+    pub edges: Vec<WeightedEdge<C>>,
+}
+
+impl<C: Scalar> SpanningTree<C> {
+    pub fn total_weight(&self) -> C {
+        self.edges.iter().fold(C::zero(), |acc, e| acc + e.weight)
+    }
+
+    /// The edge that connected the last two components, `None` for a forest.
+    pub fn last_edge(&self) -> Option<&WeightedEdge<C>> {
+        self.edges.last().filter(|_| self.is_spanning())
+    }
+
+    /// Whether all nodes are connected.
+    pub fn is_spanning(&self) -> bool {
+        self.edges.len() + 1 >= self.node_count
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Kruskal's algorithm as an iterator over the accepted edges. Stops as soon as all nodes are
+/// connected, so the last step holds the edge that connected everything.
+pub struct Kruskal<I> {
+    edges: I,
+    components: UnionFind,
+}
+
+/// Kruskal over an explicit list of edges between the nodes `0..node_count`.
+pub fn kruskal<C: Scalar>(
+    node_count: usize,
+    edges: impl IntoIterator<Item = WeightedEdge<C>>,
+) -> Kruskal<std::vec::IntoIter<WeightedEdge<C>>> {
+    kruskal_presorted(node_count, edges.into_iter().sorted_unstable())
+}
+
+/// Kruskal over all pairs of `points`, weighted by `distance`, e.g.
+/// `Point::distance_euclid_squared_from` or `Point::distance_manhattan_from`.
+pub fn kruskal_points<T: Scalar, const N: usize, C: Scalar>(
+    points: &[Point<T, N>],
+    distance: impl Fn(Point<T, N>, Point<T, N>) -> C,
+) -> Kruskal<std::vec::IntoIter<WeightedEdge<C>>> {
+    let edges = (0..points.len())
+        .tuple_combinations()
+        .map(|(a, b)| WeightedEdge {
+            weight: distance(points[a], points[b]),
+            a,
+            b,
+        });
+    kruskal(points.len(), edges)
+}
+
+/// Kruskal over edges that are already ordered by weight, e.g. the lazy pairs of
+/// `KdTree::pairs_by_distance`. Edges are only pulled from `edges` until everything is connected.
+pub fn kruskal_presorted<C, I>(node_count: usize, edges: I) -> Kruskal<I::IntoIter>
+where
+    I: IntoIterator<Item = WeightedEdge<C>>,
+{
+    Kruskal {
+        edges: edges.into_iter(),
+        components: UnionFind::new(node_count),
+    }
+}
+
+impl<C: Scalar, I: Iterator<Item = WeightedEdge<C>>> Kruskal<I> {
+    /// Current state of the forest.
+    pub fn components(&self) -> &UnionFind {
+        &self.components
+    }
+
+    /// Sizes of all components after the last step, largest first.
+    pub fn component_sizes(&self) -> Vec<usize> {
+        self.components.component_sizes()
+    }
+
+    /// Runs the algorithm to completion.
+    pub fn spanning_tree(self) -> SpanningTree<C> {
+        let node_count = self.components.len();
+        SpanningTree {
+            node_count,
+            edges: self.map(|step| step.edge).collect(),
+        }
+    }
+}
+
+impl<C: Scalar, I: Iterator<Item = WeightedEdge<C>>> Iterator for Kruskal<I> {
+    type Item = MstStep<C>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.components.component_count() <= 1 {
+            return None;
+        }
+
+        let edge = self.edges.find(|e| self.components.union(e.a, e.b))?;
+
+        Some(MstStep {
+            edge,
+            component_size: self.components.component_size(edge.a),
+            component_count: self.components.component_count(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Prim's algorithm over an explicit list of edges, growing a tree from node `0`. Nodes that are
+/// not reachable from it are not part of the result.
+pub fn prim<C: Scalar>(
+    node_count: usize,
+    edges: impl IntoIterator<Item = WeightedEdge<C>>,
+) -> SpanningTree<C> {
+    let mut adjacency: Vec<Vec<WeightedEdge<C>>> = vec![vec![]; node_count];
+    for e in edges {
+        adjacency[e.a].push(e);
+        adjacency[e.b].push(WeightedEdge {
+            weight: e.weight,
+            a: e.b,
+            b: e.a,
+        });
+    }
+
+    let mut tree = SpanningTree {
+        node_count,
+        edges: vec![],
+    };
+    if node_count == 0 {
+        return tree;
+    }
+
+    let mut in_tree = vec![false; node_count];
+    let mut queue = BinaryHeap::new();
+    in_tree[0] = true;
+    queue.extend(adjacency[0].iter().copied().map(Reverse));
+
+    while let Some(Reverse(edge)) = queue.pop() {
+        if in_tree[edge.b] {
+            continue;
+        }
+        in_tree[edge.b] = true;
+        tree.edges.push(edge);
+        queue.extend(
+            adjacency[edge.b]
+                .iter()
+                .filter(|e| !in_tree[e.b])
+                .copied()
+                .map(Reverse),
+        );
+    }
+
+    tree
+}
+
+/// Prim's algorithm over the complete graph of `points`, weighted by `distance`. Runs in
+/// `O(n²)` without materialising the edges, which beats Kruskal on dense point sets.
+pub fn prim_points<T: Scalar, const N: usize, C: Scalar>(
+    points: &[Point<T, N>],
+    distance: impl Fn(Point<T, N>, Point<T, N>) -> C,
+) -> SpanningTree<C> {
+    let node_count = points.len();
+    let mut tree = SpanningTree {
+        node_count,
+        edges: Vec::with_capacity(node_count.saturating_sub(1)),
+    };
+    if node_count == 0 {
+        return tree;
+    }
+
+    let mut in_tree = vec![false; node_count];
+    // cheapest known edge from the tree towards every node outside of it.
+    let mut best: Vec<Option<WeightedEdge<C>>> = vec![None; node_count];
+    let mut current = 0;
+
+    for _ in 1..node_count {
+        in_tree[current] = true;
+        for other in (0..node_count).filter(|&o| !in_tree[o]) {
+            let weight = distance(points[current], points[other]);
+            if best[other].is_none_or(|e| weight < e.weight) {
+                best[other] = Some(WeightedEdge {
+                    weight,
+                    a: current,
+                    b: other,
+                });
+            }
+        }
+
+        let edge = (0..node_count)
+            .filter(|&o| !in_tree[o])
+            .filter_map(|o| best[o])
+            .min()
+            .unwrap();
+        tree.edges.push(edge);
+        current = edge.b;
+    }
+
+    tree
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra_helpers::Point2;
+
+    fn get_points() -> Vec<Point2<isize>> {
+        vec![
+            Point2::new(0, 0),
+            Point2::new(0, 1),
+            Point2::new(5, 5),
+            Point2::new(6, 5),
+            Point2::new(2, 1),
+        ]
+    }
+
+    #[test]
+    fn test_kruskal_steps() {
+        let points = get_points();
+        let steps = kruskal_points(&points, Point::distance_manhattan_from).collect_vec();
+
+        assert_eq!(steps.len(), 4);
+        assert_eq!(steps[0].edge, (0, 1, 1).into());
+        assert_eq!(steps[0].component_size, 2);
+        assert_eq!(steps[1].edge, (2, 3, 1).into());
+        assert_eq!(steps[2].edge, (1, 4, 2).into());
+        assert_eq!(steps[2].component_size, 3);
+        assert_eq!(steps[2].component_count, 2);
+        assert_eq!(steps[3].edge, (2, 4, 7).into());
+        assert_eq!(steps[3].component_count, 1);
+    }
+
+    #[test]
+    fn test_kruskal_component_sizes() {
+        let points = get_points();
+        let mut kruskal = kruskal_points(&points, Point::distance_manhattan_from);
+        assert_eq!(kruskal.component_sizes(), vec![1; 5]);
+
+        let sizes = std::iter::from_fn(|| {
+            kruskal.next()?;
+            Some(kruskal.component_sizes())
+        })
+        .collect_vec();
+        assert_eq!(
+            sizes,
+            vec![vec![2, 1, 1, 1], vec![2, 2, 1], vec![3, 2], vec![5]]
+        );
+    }
+
+    #[test]
+    fn test_kruskal_and_prim_agree() {
+        let points = get_points();
+        let by_kruskal =
+            kruskal_points(&points, Point::distance_euclid_squared_from).spanning_tree();
+        let by_prim = prim_points(&points, Point::distance_euclid_squared_from);
+
+        assert!(by_kruskal.is_spanning());
+        assert!(by_prim.is_spanning());
+        assert_eq!(by_kruskal.total_weight(), 1 + 1 + 4 + 25);
+        assert_eq!(by_prim.total_weight(), by_kruskal.total_weight());
+        assert_eq!(by_kruskal.last_edge(), Some(&(2, 4, 25).into()));
+    }
+
+    #[test]
+    fn test_explicit_edges() {
+        let edges: Vec<WeightedEdge<u32>> = vec![
+            (0, 1, 4).into(),
+            (1, 2, 1).into(),
+            (0, 2, 2).into(),
+            (3, 4, 1).into(),
+        ];
+
+        let forest = kruskal(5, edges.clone()).spanning_tree();
+        assert!(!forest.is_spanning());
+        assert_eq!(forest.total_weight(), 4);
+        assert_eq!(forest.last_edge(), None);
+
+        let tree = prim(5, edges);
+        assert_eq!(tree.edges, vec![(0, 2, 2).into(), (2, 1, 1).into()]);
+    }
+}
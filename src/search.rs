@@ -0,0 +1,459 @@
+use num_traits::Signed;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+use crate::algebra_helpers::{Point, PointGrid, Scalar};
+
+/// Outcome of a search: the cost of reaching every discovered node and, for every node, all of
+/// its predecessors that lie on a shortest path towards it. Start nodes have no predecessors.
+#[derive(Debug, Clone)]
+pub struct SearchResult<N, C> {
+    pub distances: HashMap<N, C>,
+    pub predecessors: HashMap<N, Vec<N>>,
+    /// The first node for which `is_goal` returned true, if any.
+    pub goal: Option<N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> SearchResult<N, C> {
+    fn new() -> Self {
+        Self {
+            distances: HashMap::new(),
+            predecessors: HashMap::new(),
+            goal: None,
+        }
+    }
+
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.distances.get(node).copied()
+    }
+
+    pub fn goal_distance(&self) -> Option<C> {
+        self.goal.as_ref().and_then(|g| self.distance(g))
+    }
+
+    /// One shortest path from any start to `target`, both included.
+    pub fn path_to(&self, target: &N) -> Option<Vec<N>> {
+        if !self.distances.contains_key(target) {
+            return None;
+        }
+
+        let mut path = vec![target.clone()];
+        while let Some(previous) = self
+            .predecessors
+            .get(path.last().unwrap())
+            .and_then(|p| p.first())
+        {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every shortest path from any start to `target`, both included.
+    ///
+    /// The number of paths can grow exponentially, prefer `nodes_on_paths_to` if only the visited
+    /// nodes are of interest.
+    pub fn all_paths_to(&self, target: &N) -> Vec<Vec<N>> {
+        if !self.distances.contains_key(target) {
+            return vec![];
+        }
+
+        let mut paths = vec![];
+        let mut stack = vec![vec![target.clone()]];
+        while let Some(path) = stack.pop() {
+            match self.predecessors.get(path.last().unwrap()) {
+                Some(previous) if !previous.is_empty() => {
+                    for p in previous {
+                        let mut next = path.clone();
+                        next.push(p.clone());
+                        stack.push(next);
+                    }
+                }
+                _ => {
+                    paths.push(path.into_iter().rev().collect());
+                }
+            }
+        }
+        paths
+    }
+
+    /// Whether `ancestor` is `node` itself or one of its predecessors, transitively.
+    fn is_ancestor(&self, ancestor: &N, node: &N) -> bool {
+        let mut seen = HashSet::new();
+        let mut stack = vec![node];
+        while let Some(current) = stack.pop() {
+            if current == ancestor {
+                return true;
+            }
+            if seen.insert(current)
+                && let Some(previous) = self.predecessors.get(current)
+            {
+                stack.extend(previous);
+            }
+        }
+        false
+    }
+
+    /// All nodes that lie on at least one shortest path to `target`.
+    pub fn nodes_on_paths_to(&self, target: &N) -> HashSet<N> {
+        let mut nodes = HashSet::new();
+        if !self.distances.contains_key(target) {
+            return nodes;
+        }
+
+        let mut stack = vec![target.clone()];
+        while let Some(node) = stack.pop() {
+            if !nodes.insert(node.clone()) {
+                continue;
+            }
+            if let Some(previous) = self.predecessors.get(&node) {
+                stack.extend(previous.iter().cloned());
+            }
+        }
+        nodes
+    }
+}
+
+/// Breadth-first search where every step costs one.
+///
+/// Stops after all nodes at the distance of the first goal have been expanded, so the predecessors
+/// of the goal are complete. Pass `|_| false` as `is_goal` to explore everything reachable.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> SearchResult<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut result = SearchResult::new();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if result.distances.insert(start.clone(), 0).is_none() {
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(node) = queue.pop_front() {
+        let distance = result.distances[&node];
+
+        if let Some(goal_distance) = result.goal_distance()
+            && distance > goal_distance
+        {
+            break;
+        }
+
+        if result.goal.is_none() && is_goal(&node) {
+            result.goal = Some(node.clone());
+        }
+
+        for next in neighbours(&node) {
+            match result.distances.get(&next) {
+                None => {
+                    result.distances.insert(next.clone(), distance + 1);
+                    result.predecessors.insert(next.clone(), vec![node.clone()]);
+                    queue.push_back(next);
+                }
+                Some(&d) if d == distance + 1 => {
+                    result
+                        .predecessors
+                        .entry(next)
+                        .or_default()
+                        .push(node.clone());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    result
+}
+
+/// Dijkstra's algorithm for non-negative edge costs. `neighbours` yields the reachable nodes
+/// together with the cost of the step towards them. Zero-cost steps never make a start, or a node
+/// the current one was reached through, a predecessor, so the predecessors stay free of cycles.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> SearchResult<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Scalar,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, neighbours, |_| C::zero(), is_goal)
+}
+
+/// A* search. The `heuristic` must never overestimate the remaining cost to a goal, otherwise
+/// the returned goal distance might not be the shortest one.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> SearchResult<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Scalar,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut result = SearchResult::new();
+    let mut queue = BinaryHeap::new();
+
+    for start in starts {
+        if result.distances.insert(start.clone(), C::zero()).is_none() {
+            queue.push(QueueEntry {
+                estimate: heuristic(&start),
+                cost: C::zero(),
+                node: start,
+            });
+        }
+    }
+
+    while let Some(QueueEntry {
+        estimate,
+        cost,
+        node,
+    }) = queue.pop()
+    {
+        if let Some(goal_distance) = result.goal_distance()
+            && estimate > goal_distance
+        {
+            break;
+        }
+
+        // skip stale entries that have been superseded by a cheaper route.
+        if cost > result.distances[&node] {
+            continue;
+        }
+
+        if result.goal.is_none() && is_goal(&node) {
+            result.goal = Some(node.clone());
+        }
+
+        for (next, step) in neighbours(&node) {
+            debug_assert!(step >= C::zero(), "step costs must not be negative");
+            let next_cost = cost + step;
+            match result.distances.get(&next) {
+                Some(&d) if d < next_cost => {}
+                // starts have no predecessor entry. Otherwise only a zero-cost step can lead back
+                // to a node that `node` was reached through.
+                Some(&d)
+                    if d == next_cost
+                        && (!result.predecessors.contains_key(&next)
+                            || step == C::zero() && result.is_ancestor(&next, &node)) => {}
+                Some(&d) if d == next_cost => {
+                    result
+                        .predecessors
+                        .entry(next)
+                        .or_default()
+                        .push(node.clone());
+                }
+                _ => {
+                    result.distances.insert(next.clone(), next_cost);
+                    result.predecessors.insert(next.clone(), vec![node.clone()]);
+                    queue.push(QueueEntry {
+                        estimate: next_cost + heuristic(&next),
+                        cost: next_cost,
+                        node: next,
+                    });
+                }
+            }
+        }
+    }
+
+    result
+}
+
+struct QueueEntry<N, C> {
+    estimate: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> Ord for QueueEntry<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // reversed, as `BinaryHeap` is a max-heap. Ties prefer the entry that got further already.
+        other
+            .estimate
+            .cmp(&self.estimate)
+            .then_with(|| self.cost.cmp(&other.cost))
+    }
+}
+
+impl<N, C: Ord> PartialOrd for QueueEntry<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> PartialEq for QueueEntry<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<N, C: Ord> Eq for QueueEntry<N, C> {}
+
+/* -------------------------------------------------------------------------- */
+
+impl<T: Scalar + Signed, const N: usize, U> PointGrid<T, N, U> {
+    /// Orthogonal neighbours of `p` that are present in the grid and accepted by `passable`.
+    pub fn neighbours_where(
+        &self,
+        p: &Point<T, N>,
+        passable: impl Fn(&U) -> bool,
+    ) -> Vec<Point<T, N>> {
+        Point::directions()
+            .into_iter()
+            .map(|d| *p + d)
+            .filter(|n| self.get(n).is_some_and(&passable))
+            .collect()
+    }
+
+    /// Breadth-first search over orthogonal steps between `passable` cells.
+    pub fn bfs(
+        &self,
+        starts: impl IntoIterator<Item = Point<T, N>>,
+        passable: impl Fn(&U) -> bool,
+        is_goal: impl FnMut(&Point<T, N>) -> bool,
+    ) -> SearchResult<Point<T, N>, usize> {
+        bfs(starts, |p| self.neighbours_where(p, &passable), is_goal)
+    }
+
+    /// Dijkstra over orthogonal steps, where `cost` returns the cost of entering a cell or `None`
+    /// if the cell cannot be entered.
+    pub fn dijkstra<C: Scalar>(
+        &self,
+        starts: impl IntoIterator<Item = Point<T, N>>,
+        cost: impl Fn(&U) -> Option<C>,
+        is_goal: impl FnMut(&Point<T, N>) -> bool,
+    ) -> SearchResult<Point<T, N>, C> {
+        dijkstra(starts, |p| self.weighted_neighbours(p, &cost), is_goal)
+    }
+
+    /// A* over orthogonal steps towards a single `goal`, using the manhattan distance as
+    /// heuristic. Entering a cell must therefore cost at least one.
+    pub fn astar(
+        &self,
+        starts: impl IntoIterator<Item = Point<T, N>>,
+        goal: Point<T, N>,
+        cost: impl Fn(&U) -> Option<T>,
+    ) -> SearchResult<Point<T, N>, T> {
+        astar(
+            starts,
+            |p| self.weighted_neighbours(p, &cost),
+            |p| p.distance_manhattan_from(goal),
+            |p| *p == goal,
+        )
+    }
+
+    fn weighted_neighbours<C>(
+        &self,
+        p: &Point<T, N>,
+        cost: impl Fn(&U) -> Option<C>,
+    ) -> Vec<(Point<T, N>, C)> {
+        Point::directions()
+            .into_iter()
+            .map(|d| *p + d)
+            .filter_map(|n| Some((n, cost(self.get(&n)?)?)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra_helpers::Point2;
+
+    fn get_maze() -> PointGrid<isize, 2, char> {
+        let (grid, _) = PointGrid::parse("S..\n.#.\n..E", &[], &[], Some).unwrap();
+        grid
+    }
+
+    #[test]
+    fn test_bfs_on_graph() {
+        let result = bfs([0_u32], |n| [n + 1, n * 3], |n| *n == 10);
+        assert_eq!(result.goal, Some(10));
+        assert_eq!(result.goal_distance(), Some(4));
+        assert_eq!(result.path_to(&10), Some(vec![0, 1, 3, 9, 10]));
+    }
+
+    #[test]
+    fn test_bfs_multiple_starts() {
+        let result = bfs([0_i32, 20], |n| [n - 1, n + 1], |n| *n == 15);
+        assert_eq!(result.goal_distance(), Some(5));
+        assert_eq!(result.path_to(&15), Some(vec![20, 19, 18, 17, 16, 15]));
+    }
+
+    #[test]
+    fn test_grid_bfs_all_paths() {
+        let maze = get_maze();
+        let result = maze.bfs(
+            [Point2::new(0, 0)],
+            |c| *c != '#',
+            |p| *p == Point2::new(2, 2),
+        );
+        assert_eq!(result.goal_distance(), Some(4));
+
+        let paths = result.all_paths_to(&Point2::new(2, 2));
+        assert_eq!(paths.len(), 2);
+        assert!(paths.iter().all(|p| p.len() == 5));
+        assert_eq!(result.nodes_on_paths_to(&Point2::new(2, 2)).len(), 8);
+    }
+
+    #[test]
+    fn test_grid_dijkstra_and_astar() {
+        let maze = get_maze();
+        let cost = |c: &char| (*c != '#').then_some(1);
+        let start = Point2::new(0, 0);
+        let goal = Point2::new(2, 2);
+
+        let result = maze.dijkstra([start], cost, |p| *p == goal);
+        assert_eq!(result.goal_distance(), Some(4));
+        assert_eq!(result.all_paths_to(&goal).len(), 2);
+
+        let result = maze.astar([start], goal, cost);
+        assert_eq!(result.goal_distance(), Some(4));
+        assert_eq!(result.all_paths_to(&goal).len(), 2);
+    }
+
+    #[test]
+    fn test_dijkstra_weighted_graph() {
+        let edges: HashMap<char, Vec<(char, u32)>> = HashMap::from([
+            ('a', vec![('b', 7), ('c', 2)]),
+            ('c', vec![('b', 3), ('d', 8)]),
+            ('b', vec![('d', 1)]),
+        ]);
+        let result = dijkstra(
+            ['a'],
+            |n| edges.get(n).cloned().unwrap_or_default(),
+            |n| *n == 'd',
+        );
+        assert_eq!(result.goal_distance(), Some(6));
+        assert_eq!(result.path_to(&'d'), Some(vec!['a', 'c', 'b', 'd']));
+        assert_eq!(result.distance(&'b'), Some(5));
+    }
+
+    #[test]
+    fn test_dijkstra_zero_cost_cycle() {
+        let edges: HashMap<char, Vec<(char, u32)>> = HashMap::from([
+            ('s', vec![('a', 0)]),
+            ('a', vec![('s', 0), ('a', 0), ('b', 1)]),
+        ]);
+        let result = dijkstra(
+            ['s'],
+            |n| edges.get(n).cloned().unwrap_or_default(),
+            |_| false,
+        );
+        assert_eq!(result.distance(&'a'), Some(0));
+        assert_eq!(result.predecessors.get(&'s'), None);
+        assert_eq!(result.path_to(&'a'), Some(vec!['s', 'a']));
+        assert_eq!(result.all_paths_to(&'b'), vec![vec!['s', 'a', 'b']]);
+        assert_eq!(result.nodes_on_paths_to(&'b').len(), 3);
+    }
+}
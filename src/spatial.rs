@@ -0,0 +1,364 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::algebra_helpers::{Point, Scalar};
+
+/// Distance functions supported by the spatial index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Metric {
+    /// Squared euclidean distance, like `Point::distance_euclid_squared_from`.
+    EuclidSquared,
+    /// Manhattan distance, like `Point::distance_manhattan_from`.
+    Manhattan,
+}
+
+impl Metric {
+    pub fn distance<T: Scalar, const N: usize>(&self, a: &Point<T, N>, b: &Point<T, N>) -> T {
+        (0..N).fold(T::zero(), |acc, i| {
+            acc + self.axis_distance(abs_diff(a.0[i], b.0[i]))
+        })
+    }
+
+    /// Contribution of a difference of `diff` along a single axis.
+    fn axis_distance<T: Scalar>(&self, diff: T) -> T {
+        match self {
+            Self::EuclidSquared => diff * diff,
+            Self::Manhattan => diff,
+        }
+    }
+}
+
+fn abs_diff<T: Scalar>(a: T, b: T) -> T {
+    if a > b { a - b } else { b - a }
+}
+
+/// A k-d tree over a fixed set of points. Query results refer to points by their index in the
+/// slice the tree was built from and are ordered by increasing distance.
+#[derive(Debug, Clone)]
+pub struct KdTree<T: Scalar, const N: usize> {
+    // implicit balanced tree: the root of every subslice is its middle element.
+    nodes: Vec<(usize, Point<T, N>)>,
+}
+
+impl<T: Scalar, const N: usize> KdTree<T, N> {
+    pub fn new(points: &[Point<T, N>]) -> Self {
+        let mut nodes: Vec<(usize, Point<T, N>)> = points.iter().copied().enumerate().collect();
+        Self::build(&mut nodes, 0);
+        Self { nodes }
+    }
+
+    fn build(nodes: &mut [(usize, Point<T, N>)], depth: usize) {
+        if nodes.len() <= 1 {
+            return;
+        }
+
+        let axis = depth % N;
+        let mid = nodes.len() / 2;
+        nodes.select_nth_unstable_by_key(mid, |(_, p)| p.0[axis]);
+
+        let (lower, upper) = nodes.split_at_mut(mid);
+        Self::build(lower, depth + 1);
+        Self::build(&mut upper[1..], depth + 1);
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// The `k` points closest to `target` as `(index, distance)`.
+    pub fn nearest(&self, target: &Point<T, N>, k: usize, metric: Metric) -> Vec<(usize, T)> {
+        self.nearest_after(target, k, metric, None)
+    }
+
+    /// Like `nearest`, only considering points with an index larger than `after`, if given.
+    fn nearest_after(
+        &self,
+        target: &Point<T, N>,
+        k: usize,
+        metric: Metric,
+        after: Option<usize>,
+    ) -> Vec<(usize, T)> {
+        let mut best = Nearest {
+            k,
+            after,
+            heap: BinaryHeap::with_capacity(k + 1),
+        };
+        if k > 0 {
+            self.nearest_in(&self.nodes, 0, target, metric, &mut best);
+        }
+        let mut result: Vec<(usize, T)> = best.heap.into_iter().map(|(d, i)| (i, d)).collect();
+        result.sort_unstable_by_key(|&(i, d)| (d, i));
+        result
+    }
+
+    fn nearest_in(
+        &self,
+        nodes: &[(usize, Point<T, N>)],
+        depth: usize,
+        target: &Point<T, N>,
+        metric: Metric,
+        best: &mut Nearest<T>,
+    ) {
+        if nodes.is_empty() {
+            return;
+        }
+
+        let axis = depth % N;
+        let mid = nodes.len() / 2;
+        let (index, point) = nodes[mid];
+
+        best.offer(metric.distance(&point, target), index);
+
+        let (near, far) = if target.0[axis] < point.0[axis] {
+            (&nodes[..mid], &nodes[mid + 1..])
+        } else {
+            (&nodes[mid + 1..], &nodes[..mid])
+        };
+
+        self.nearest_in(near, depth + 1, target, metric, best);
+
+        let axis_distance = metric.axis_distance(abs_diff(target.0[axis], point.0[axis]));
+        if best.heap.len() < best.k
+            || best
+                .heap
+                .peek()
+                .is_some_and(|&(worst, _)| axis_distance <= worst)
+        {
+            self.nearest_in(far, depth + 1, target, metric, best);
+        }
+    }
+
+    /// All points with a distance of at most `radius` to `target` as `(index, distance)`.
+    ///
+    /// The radius is measured in the given metric, i.e. it is squared for `EuclidSquared`.
+    pub fn within(&self, target: &Point<T, N>, radius: T, metric: Metric) -> Vec<(usize, T)> {
+        let mut result = vec![];
+        self.within_in(&self.nodes, 0, target, radius, metric, &mut result);
+        result.sort_unstable_by_key(|&(i, d)| (d, i));
+        result
+    }
+
+    fn within_in(
+        &self,
+        nodes: &[(usize, Point<T, N>)],
+        depth: usize,
+        target: &Point<T, N>,
+        radius: T,
+        metric: Metric,
+        result: &mut Vec<(usize, T)>,
+    ) {
+        if nodes.is_empty() {
+            return;
+        }
+
+        let axis = depth % N;
+        let mid = nodes.len() / 2;
+        let (index, point) = nodes[mid];
+
+        let distance = metric.distance(&point, target);
+        if distance <= radius {
+            result.push((index, distance));
+        }
+
+        let axis_distance = metric.axis_distance(abs_diff(target.0[axis], point.0[axis]));
+        if target.0[axis] < point.0[axis] || axis_distance <= radius {
+            self.within_in(&nodes[..mid], depth + 1, target, radius, metric, result);
+        }
+        if target.0[axis] >= point.0[axis] || axis_distance <= radius {
+            self.within_in(&nodes[mid + 1..], depth + 1, target, radius, metric, result);
+        }
+    }
+
+    /// Lazily yields every unordered pair of points as `(index_a, index_b, distance)` with
+    /// `index_a < index_b`, closest pairs first.
+    pub fn pairs_by_distance(&self, metric: Metric) -> PairsByDistance<'_, T, N> {
+        let mut points = vec![Point::zero(); self.len()];
+        for &(index, point) in &self.nodes {
+            points[index] = point;
+        }
+
+        let mut pairs = PairsByDistance {
+            tree: self,
+            metric,
+            streams: (0..self.len())
+                .map(|_| NeighbourStream {
+                    fetched: vec![],
+                    position: 0,
+                })
+                .collect(),
+            points,
+            queue: BinaryHeap::new(),
+        };
+        for index in 0..pairs.points.len() {
+            pairs.push_next(index);
+        }
+        pairs
+    }
+}
+
+/// The `k` closest points found so far, as a max-heap of `(distance, index)`.
+struct Nearest<T> {
+    k: usize,
+    after: Option<usize>,
+    heap: BinaryHeap<(T, usize)>,
+}
+
+impl<T: Scalar> Nearest<T> {
+    fn offer(&mut self, distance: T, index: usize) {
+        if self.after.is_some_and(|after| index <= after) {
+            return;
+        }
+        if self.heap.len() < self.k {
+            self.heap.push((distance, index));
+        } else if self
+            .heap
+            .peek()
+            .is_some_and(|&worst| (distance, index) < worst)
+        {
+            self.heap.pop();
+            self.heap.push((distance, index));
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct NeighbourStream<T> {
+    fetched: Vec<(usize, T)>,
+    position: usize,
+}
+
+/// Iterator returned by `KdTree::pairs_by_distance`.
+///
+/// Keeps one lazily extended nearest-neighbour list per point and merges them with a heap, so
+/// taking the closest `k` pairs does not require computing all of them. A list only holds points
+/// with a larger index, and is refilled by querying the tree again with twice as many neighbours,
+/// capped at the number of such points. Creating the iterator costs `n` queries, after that the
+/// queries of a point cost `O(m log n)` in total once `m` of its pairs have been taken, and its
+/// list holds at most `max(2m, 8)` neighbours.
+pub struct PairsByDistance<'a, T: Scalar, const N: usize> {
+    tree: &'a KdTree<T, N>,
+    metric: Metric,
+    points: Vec<Point<T, N>>,
+    streams: Vec<NeighbourStream<T>>,
+    queue: BinaryHeap<Reverse<(T, usize, usize)>>,
+}
+
+impl<T: Scalar, const N: usize> PairsByDistance<'_, T, N> {
+    /// Queues the next neighbour of `index` with a larger index than itself, if any.
+    fn push_next(&mut self, index: usize) {
+        let stream = &mut self.streams[index];
+        if stream.position == stream.fetched.len() {
+            let higher = self.points.len() - index - 1;
+            if stream.position >= higher {
+                return;
+            }
+            let k = (stream.fetched.len() * 2).max(8).min(higher);
+            stream.fetched =
+                self.tree
+                    .nearest_after(&self.points[index], k, self.metric, Some(index));
+        }
+
+        let (other, distance) = stream.fetched[stream.position];
+        stream.position += 1;
+        self.queue.push(Reverse((distance, index, other)));
+    }
+}
+
+impl<T: Scalar, const N: usize> Iterator for PairsByDistance<'_, T, N> {
+    type Item = (usize, usize, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((distance, a, b)) = self.queue.pop()?;
+        self.push_next(a);
+        Some((a, b, distance))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra_helpers::{Point2, Point3};
+    use itertools::Itertools;
+
+    fn get_points() -> Vec<Point2<isize>> {
+        (0..7)
+            .cartesian_product(0..5)
+            .map(|(x, y)| Point2::new(x * 3 - 4, y * y - 2 * x))
+            .collect()
+    }
+
+    #[test]
+    fn test_nearest() {
+        let points = get_points();
+        let tree = KdTree::new(&points);
+        let target = Point2::new(1, 1);
+
+        for metric in [Metric::EuclidSquared, Metric::Manhattan] {
+            let expected = points
+                .iter()
+                .enumerate()
+                .map(|(i, p)| (i, metric.distance(p, &target)))
+                .sorted_by_key(|&(i, d)| (d, i))
+                .take(5)
+                .collect_vec();
+            assert_eq!(tree.nearest(&target, 5, metric), expected);
+        }
+        assert_eq!(tree.nearest(&target, 100, Metric::Manhattan).len(), 35);
+    }
+
+    #[test]
+    fn test_within() {
+        let points = get_points();
+        let tree = KdTree::new(&points);
+        let target = Point2::new(2, 3);
+
+        let expected = points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (i, p.distance_euclid_squared_from(target)))
+            .filter(|&(_, d)| d <= 20)
+            .sorted_by_key(|&(i, d)| (d, i))
+            .collect_vec();
+        assert_eq!(tree.within(&target, 20, Metric::EuclidSquared), expected);
+    }
+
+    #[test]
+    fn test_pairs_by_distance() {
+        let points = vec![
+            Point3::new(0_isize, 0, 0),
+            Point3::new(10, 0, 0),
+            Point3::new(1, 1, 0),
+            Point3::new(10, 3, 0),
+            Point3::new(50, 50, 50),
+        ];
+        let tree = KdTree::new(&points);
+        let pairs = tree.pairs_by_distance(Metric::EuclidSquared).collect_vec();
+
+        assert_eq!(pairs.len(), 10);
+        assert_eq!(pairs[0], (0, 2, 2));
+        assert_eq!(pairs[1], (1, 3, 9));
+        assert!(pairs.iter().all(|(a, b, _)| a < b));
+        assert!(pairs.windows(2).all(|w| w[0].2 <= w[1].2));
+        assert_eq!(
+            pairs.iter().map(|&(a, b, _)| (a, b)).sorted().collect_vec(),
+            (0..5).tuple_combinations().collect_vec()
+        );
+    }
+
+    #[test]
+    fn test_pairs_by_distance_fetches_larger_indices_only() {
+        let points = get_points();
+        let tree = KdTree::new(&points);
+        let mut pairs = tree.pairs_by_distance(Metric::Manhattan);
+        pairs.by_ref().take(10).for_each(drop);
+
+        for (index, stream) in pairs.streams.iter().enumerate() {
+            assert!(stream.fetched.len() <= (points.len() - index - 1).min(8));
+            assert!(stream.fetched.iter().all(|&(other, _)| other > index));
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, Year, data_dir};
+
+static ANSWERS_FILE_NAME: &str = "answers.json";
+
+/// Known-good answers for a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answer {
+    pub day: Day,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+/// Known-good answers for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self, year: Option<Year>) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(data_dir(year).join(ANSWERS_FILE_NAME))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns empty answers.
+    /// Fails if the file can't be read or parsed, so that it isn't replaced by a single answer.
+    pub fn read_from_file(year: Option<Year>) -> Result<Self, String> {
+        match fs::read_to_string(data_dir(year).join(ANSWERS_FILE_NAME)) {
+            Ok(json) => Answers::try_from(json),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// The stored answer for one part of a day, if known.
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        let answer = self.data.iter().find(|a| a.day == day)?;
+        match part {
+            1 => answer.part_1.as_deref(),
+            2 => answer.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Store `value` as the answer for one part of a day, replacing a previous answer.
+    pub fn set(&mut self, day: Day, part: u8, value: &str) {
+        let index = match self.data.iter().position(|a| a.day == day) {
+            Some(index) => index,
+            None => {
+                self.data.push(Answer {
+                    day,
+                    part_1: None,
+                    part_2: None,
+                });
+                self.data.sort_unstable_by_key(|a| a.day);
+                self.data.iter().position(|a| a.day == day).unwrap()
+            }
+        };
+
+        let answer = &mut self.data[index];
+        match part {
+            1 => answer.part_1 = Some(value.into()),
+            2 => answer.part_2 = Some(value.into()),
+            _ => {}
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+
+        for (key, answer) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            map.insert(
+                key.into(),
+                match answer {
+                    Some(x) => JsonValue::String(x.clone()),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        let part = |key: &str| match json.get(key) {
+            Some(v) if !v.is_null() => v
+                .get::<String>()
+                .cloned()
+                .map(Some)
+                .ok_or(format!("Expected answer.{key} to be null or string.")),
+            _ => Ok(None),
+        };
+
+        Ok(Answer {
+            day,
+            part_1: part("part_1")?,
+            part_2: part("part_2")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::Answers;
+    use crate::day;
+
+    #[test]
+    fn handles_json_answers() {
+        let json =
+            r#"{ "data": [{ "day": "07", "part_1": "1651", "part_2": null }, { "day": "08" }] }"#
+                .to_string();
+        let answers = Answers::try_from(json).unwrap();
+        assert_eq!(answers.data.len(), 2);
+        assert_eq!(answers.get(day!(7), 1), Some("1651"));
+        assert_eq!(answers.get(day!(7), 2), None);
+        assert_eq!(answers.get(day!(8), 1), None);
+        assert_eq!(answers.get(day!(9), 1), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_answers() {
+        let json = r#"{ "data": [{ "day": "07", "part_1": 1651 }] }"#.to_string();
+        Answers::try_from(json).unwrap();
+    }
+
+    #[test]
+    fn sets_answers() {
+        let mut answers = Answers::default();
+        answers.set(day!(8), 2, "25272");
+        answers.set(day!(3), 1, "357");
+        answers.set(day!(8), 2, "40");
+
+        assert_eq!(answers.data.len(), 2);
+        assert_eq!(answers.data[0].day, day!(3));
+        assert_eq!(answers.get(day!(8), 2), Some("40"));
+
+        let json = JsonValue::from(answers.clone()).stringify().unwrap();
+        let roundtrip = Answers::try_from(json).unwrap();
+        assert_eq!(roundtrip.data, answers.data);
+    }
+}
@@ -0,0 +1,599 @@
+/// Client for the Advent of Code website, used to download puzzles and inputs and to submit
+/// answers.
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+
+use ureq::Agent;
+
+use crate::template::{
+    PuzzleId,
+    download_state::{DownloadState, MIN_REQUEST_INTERVAL},
+};
+
+/// Environment variable with the session token, i.e. the value of the `session` cookie.
+pub const SESSION_ENV: &str = "AOC_SESSION";
+/// File in the home directory that holds the session token, if it is not set in the environment.
+/// This is the same file that aoc-cli reads.
+pub const SESSION_FILE: &str = ".adventofcode.session";
+/// Environment variable to point the client to another server, e.g. a local stub.
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+
+static DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+static USER_AGENT: &str = concat!("advent_of_code/", env!("CARGO_PKG_VERSION"));
+/// Heading that appears in the description once part one is solved.
+pub(crate) static PART_TWO_HEADING: &str = "--- Part Two ---";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    SessionNotFound,
+    YearNotFound,
+    Request(String),
+    BadStatus(u16),
+    Io(std::io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "no session token found. Set {SESSION_ENV} or store it in ~/{SESSION_FILE}."
+            ),
+            AocClientError::YearNotFound => {
+                write!(f, "no year given. Set AOC_YEAR or pass --year.")
+            }
+            AocClientError::Request(e) => write!(f, "request failed: {e}"),
+            AocClientError::BadStatus(status) => {
+                write!(f, "server responded with status {status}.")
+            }
+            AocClientError::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<std::io::Error> for AocClientError {
+    fn from(e: std::io::Error) -> Self {
+        AocClientError::Io(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        AocClientError::Request(e.to_string())
+    }
+}
+
+pub struct AocClient {
+    agent: Agent,
+    base_url: String,
+    session: String,
+    /// Minimum interval between requests, tracked in the download state. `None` to not track.
+    interval: Option<Duration>,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        let agent = Agent::config_builder()
+            .http_status_as_error(false)
+            .build()
+            .into();
+
+        AocClient {
+            agent,
+            base_url: base_url.trim_end_matches('/').into(),
+            session: session.trim().into(),
+            interval: None,
+        }
+    }
+
+    /// Keep at least `interval` between requests, across runs.
+    #[must_use]
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    /// A client for the server in `AOC_BASE_URL`, or the Advent of Code website, that
+    /// authenticates with the token in `AOC_SESSION` or `~/.adventofcode.session`.
+    /// Requests are throttled to [`MIN_REQUEST_INTERVAL`].
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let base_url = env::var(BASE_URL_ENV).unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        let session = env::var(SESSION_ENV)
+            .ok()
+            .or_else(|| fs::read_to_string(home_dir()?.join(SESSION_FILE)).ok())
+            .filter(|session| !session.trim().is_empty())
+            .ok_or(AocClientError::SessionNotFound)?;
+
+        Ok(AocClient::new(&base_url, &session).with_interval(MIN_REQUEST_INTERVAL))
+    }
+
+    /// Waits until the interval since the last request has passed and notes the new request.
+    fn throttle(&self) {
+        let Some(interval) = self.interval else {
+            return;
+        };
+
+        let mut state = DownloadState::read_from_file();
+        if let Some(wait) = state.wait_time(interval) {
+            println!(
+                "Waiting {:.1}s before the next request...",
+                wait.as_secs_f64()
+            );
+            thread::sleep(wait);
+        }
+
+        state.record();
+        if let Err(e) = state.store_file() {
+            eprintln!("Failed to store download state: {e}");
+        }
+    }
+
+    fn url(&self, puzzle: PuzzleId, path: &str) -> Result<String, AocClientError> {
+        let year = puzzle.year().ok_or(AocClientError::YearNotFound)?;
+        Ok(format!(
+            "{}/{year}/day/{}{path}",
+            self.base_url,
+            puzzle.day.into_inner()
+        ))
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    fn read_body(mut response: ureq::http::Response<ureq::Body>) -> Result<String, AocClientError> {
+        let status = response.status().as_u16();
+        if !(200..300).contains(&status) {
+            return Err(AocClientError::BadStatus(status));
+        }
+        Ok(response.body_mut().read_to_string()?)
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocClientError> {
+        self.throttle();
+        let response = self
+            .agent
+            .get(url)
+            .header("Cookie", &self.cookie())
+            .header("User-Agent", USER_AGENT)
+            .call()?;
+        Self::read_body(response)
+    }
+
+    /// The personal puzzle input.
+    pub fn input(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        self.get(&self.url(puzzle, "/input")?)
+    }
+
+    /// The puzzle description as Markdown, including part two once it is unlocked.
+    pub fn puzzle(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let html = self.get(&self.url(puzzle, "")?)?;
+        Ok(html_to_markdown(&html))
+    }
+
+    /// Submit an answer and return the response as Markdown, see `Verdict::from_response`.
+    pub fn submit(
+        &self,
+        puzzle: PuzzleId,
+        part: u8,
+        answer: &str,
+    ) -> Result<String, AocClientError> {
+        let url = self.url(puzzle, "/answer")?;
+        self.throttle();
+        let response = self
+            .agent
+            .post(&url)
+            .header("Cookie", &self.cookie())
+            .header("User-Agent", USER_AGENT)
+            .send_form([("level", part.to_string().as_str()), ("answer", answer)])?;
+        let html = Self::read_body(response)?;
+        Ok(html_to_markdown(&html))
+    }
+}
+
+fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<(), AocClientError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+/// A previously downloaded file, if it is not empty.
+fn cached(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .filter(|contents| !contents.trim().is_empty())
+}
+
+/// Whether a cached description is final. Until part two is unlocked, part one may have been
+/// solved since it was downloaded.
+fn is_complete(markdown: &str) -> bool {
+    markdown.contains(PART_TWO_HEADING)
+}
+
+/// Print the puzzle description. It is downloaded again unless it is complete, or a request was
+/// made too recently. `force` skips the cache, but still waits for the minimum interval.
+pub fn read(puzzle: PuzzleId, force: bool) -> Result<(), AocClientError> {
+    let puzzle_path = puzzle.data_path("puzzles", "md");
+
+    let markdown = match cached(&puzzle_path) {
+        Some(markdown)
+            if !force
+                && (is_complete(&markdown)
+                    || DownloadState::read_from_file()
+                        .wait_time(MIN_REQUEST_INTERVAL)
+                        .is_some()) =>
+        {
+            markdown
+        }
+        _ => {
+            let markdown = AocClient::from_env()?.puzzle(puzzle)?;
+            write_file(&puzzle_path, &markdown)?;
+            markdown
+        }
+    };
+
+    println!("{markdown}");
+    Ok(())
+}
+
+/// Download the puzzle input and description. The input never changes and is only downloaded
+/// once, the description is refreshed until part two is unlocked. `force` skips the cache, but
+/// still waits for the minimum interval.
+pub fn download(puzzle: PuzzleId, force: bool) -> Result<(), AocClientError> {
+    let input_path = puzzle.data_path("inputs", "txt");
+    let puzzle_path = puzzle.data_path("puzzles", "md");
+
+    let fetch_input = force || cached(&input_path).is_none();
+    let fetch_puzzle = force || !cached(&puzzle_path).is_some_and(|md| is_complete(&md));
+
+    if fetch_input || fetch_puzzle {
+        let client = AocClient::from_env()?;
+        if fetch_input {
+            write_file(&input_path, &client.input(puzzle)?)?;
+        }
+        if fetch_puzzle {
+            write_file(&puzzle_path, &client.puzzle(puzzle)?)?;
+        }
+    }
+
+    println!("---");
+    for (path, fetched, what) in [
+        (&input_path, fetch_input, "input"),
+        (&puzzle_path, fetch_puzzle, "puzzle"),
+    ] {
+        if fetched {
+            println!("🎄 Successfully wrote {what} to \"{}\".", path.display());
+        } else {
+            println!(
+                "🎄 Using cached {what} in \"{}\", pass --force to download it again.",
+                path.display()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Submit an answer, print the response and return it.
+pub fn submit(puzzle: PuzzleId, part: u8, result: &str) -> Result<String, AocClientError> {
+    let response = AocClient::from_env()?.submit(puzzle, part, result)?;
+    println!("{response}");
+    Ok(response)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Converts the `<article>` elements of a page to Markdown. Puzzle descriptions as well as the
+/// responses to submissions are wrapped in articles, the rest of the page is navigation.
+pub fn html_to_markdown(html: &str) -> String {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(end) = rest[start..].find("</article>") else {
+            break;
+        };
+        articles.push(convert_fragment(&rest[start..start + end]));
+        rest = &rest[start + end + "</article>".len()..];
+    }
+
+    articles.join("\n\n")
+}
+
+fn convert_fragment(html: &str) -> String {
+    let mut out = String::new();
+    let mut in_pre = false;
+    let mut links: Vec<String> = vec![];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            out.push_str(&decode_entities(rest));
+            break;
+        };
+        out.push_str(&decode_entities(&rest[..start]));
+
+        let Some(end) = rest[start..].find('>') else {
+            break;
+        };
+        let tag = &rest[start + 1..start + end];
+        rest = &rest[start + end + 1..];
+
+        let is_closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        match (name.as_str(), is_closing) {
+            ("h2", false) => out.push_str("## "),
+            ("h2" | "p" | "ul", true) => out.push_str("\n\n"),
+            ("pre", false) => {
+                in_pre = true;
+                out.push_str("```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+            }
+            ("code", _) if !in_pre => out.push('`'),
+            ("em", _) if !in_pre => out.push('*'),
+            ("li", false) => out.push_str("- "),
+            ("li", true) | ("br", _) => out.push('\n'),
+            ("a", false) => {
+                links.push(attribute(tag, "href").unwrap_or_default());
+                out.push('[');
+            }
+            ("a", true) => {
+                let href = links.pop().unwrap_or_default();
+                out.push_str(&format!("]({href})"));
+            }
+            _ => {}
+        }
+    }
+
+    // collapse the blank lines left behind by nested blocks.
+    let mut markdown = out.trim().to_string();
+    while markdown.contains("\n\n\n") {
+        markdown = markdown.replace("\n\n\n", "\n\n");
+    }
+    markdown
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let start = tag.find(&format!("{name}=\""))? + name.len() + 2;
+    let end = tag[start..].find('"')?;
+    Some(decode_entities(&tag[start..start + end]))
+}
+
+fn decode_entities(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest
+            .find(';')
+            .filter(|&end| end <= 10)
+            .and_then(|end| Some((decode_entity(&rest[1..end])?, end)));
+
+        match entity {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+fn decode_entity(entity: &str) -> Option<char> {
+    match entity {
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "amp" => Some('&'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some(' '),
+        _ => {
+            let code = entity.strip_prefix('#')?;
+            let code = match code.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => code.parse().ok()?,
+            };
+            char::from_u32(code)
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::{Arc, Mutex},
+        thread,
+    };
+
+    use super::{AocClient, AocClientError, html_to_markdown, is_complete};
+    use crate::{day, template::PuzzleId, template::Year};
+
+    const PUZZLE_HTML: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Test ---</h2><p>Find the <em>sum</em> of <code>a &lt; b</code>, see <a href="/2024/about">here</a>.</p>
+<pre><code>1
+<em>2</em>
+</code></pre>
+<ul><li>one</li><li>two</li></ul></article>
+<p>Your puzzle answer was <code>42</code>.</p>
+</main></body></html>"#;
+
+    /// Serves canned responses by request line and records the requests it received.
+    fn stub_server(
+        routes: Vec<(&'static str, u16, &'static str)>,
+    ) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(vec![]));
+        let recorded = Arc::clone(&requests);
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut head = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(length) = line.to_ascii_lowercase().strip_prefix("content-length:")
+                    {
+                        content_length = length.trim().parse().unwrap();
+                    }
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                    head.push_str(&line);
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+
+                let request_line = head.lines().next().unwrap_or_default().to_string();
+                let (status, response) = routes
+                    .iter()
+                    .find(|(route, _, _)| request_line.starts_with(route))
+                    .map_or((404, ""), |&(_, status, response)| (status, response));
+
+                recorded
+                    .lock()
+                    .unwrap()
+                    .push(format!("{head}\n{}", String::from_utf8_lossy(&body)));
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+                    response.len()
+                )
+                .unwrap();
+            }
+        });
+
+        (address, requests)
+    }
+
+    fn puzzle() -> PuzzleId {
+        PuzzleId {
+            year: Year::new(2024),
+            day: day!(1),
+        }
+    }
+
+    #[test]
+    fn converts_puzzle_html_to_markdown() {
+        assert_eq!(
+            html_to_markdown(PUZZLE_HTML),
+            [
+                "## --- Day 1: Test ---",
+                "",
+                "Find the *sum* of `a < b`, see [here](/2024/about).",
+                "",
+                "```",
+                "1",
+                "2",
+                "```",
+                "",
+                "- one",
+                "- two",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn detects_unlocked_part_two() {
+        assert!(!is_complete(&html_to_markdown(PUZZLE_HTML)));
+
+        let html = PUZZLE_HTML.replace(
+            "</main>",
+            "<article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2></article></main>",
+        );
+        assert!(is_complete(&html_to_markdown(&html)));
+    }
+
+    #[test]
+    fn downloads_input_and_puzzle() {
+        let (address, requests) = stub_server(vec![
+            ("GET /2024/day/1/input ", 200, "1\n2\n"),
+            ("GET /2024/day/1 ", 200, PUZZLE_HTML),
+        ]);
+        let client = AocClient::new(&address, "secret\n");
+
+        assert_eq!(client.input(puzzle()).unwrap(), "1\n2\n");
+        assert!(
+            client
+                .puzzle(puzzle())
+                .unwrap()
+                .starts_with("## --- Day 1: Test ---")
+        );
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].contains("cookie: session=secret\r\n"));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (address, requests) = stub_server(vec![(
+            "POST /2024/day/1/answer ",
+            200,
+            "<main><article><p>That's not the right answer; your answer is too high.</p></article></main>",
+        )]);
+        let client = AocClient::new(&address, "secret");
+
+        let response = client.submit(puzzle(), 2, "1234").unwrap();
+        assert_eq!(
+            response,
+            "That's not the right answer; your answer is too high."
+        );
+        assert!(requests.lock().unwrap()[0].ends_with("level=2&answer=1234"));
+    }
+
+    #[test]
+    fn reports_bad_status() {
+        let (address, _) = stub_server(vec![("GET /2024/day/1/input ", 400, "log in")]);
+        let client = AocClient::new(&address, "expired");
+
+        assert!(matches!(
+            client.input(puzzle()),
+            Err(AocClientError::BadStatus(400))
+        ));
+    }
+}
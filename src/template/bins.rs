@@ -0,0 +1,275 @@
+/// Builds the solution bins with a single `cargo build` and locates the produced executables, so
+/// that they can be invoked directly instead of going through `cargo run` for every day.
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, BufReader},
+    path::PathBuf,
+    process::{Command, Stdio},
+    str::FromStr,
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::PuzzleId;
+
+/// The cargo profile to build the bins with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Profile {
+    Debug,
+    Release,
+    /// Release build with heap profiling, see `cargo solve --dhat`.
+    Dhat,
+}
+
+impl Profile {
+    pub fn cargo_args(self) -> &'static [&'static str] {
+        match self {
+            Profile::Debug => &[],
+            Profile::Release => &["--release"],
+            Profile::Dhat => &["--profile", "dhat", "--features", "dhat-heap"],
+        }
+    }
+}
+
+/// How to start the bin of a day.
+pub enum Launcher {
+    /// Through `cargo run`, compiling the bin on demand.
+    Cargo(Profile),
+    /// Directly from the target directory after building all bins up front.
+    Built(BuiltBins),
+}
+
+impl Launcher {
+    /// Builds all bins, or only the bin of `puzzle`, up front.
+    pub fn built(profile: Profile, puzzle: Option<PuzzleId>) -> Self {
+        match build(profile, puzzle) {
+            Ok(bins) => Launcher::Built(bins),
+            Err(e) => {
+                eprintln!("Could not run cargo build ({e}), falling back to cargo run.");
+                Launcher::Cargo(profile)
+            }
+        }
+    }
+
+    /// A command that runs the bin of `puzzle` with `args`. Fails with the compiler errors if
+    /// the bin could not be built.
+    pub fn command(&self, puzzle: PuzzleId, args: &[&str]) -> Result<Command, String> {
+        match self {
+            Launcher::Cargo(profile) => {
+                let bin_name = puzzle.to_string();
+                let mut cmd = Command::new("cargo");
+                cmd.args(["run", "--quiet", "--bin", &bin_name])
+                    .args(profile.cargo_args());
+                if !args.is_empty() {
+                    cmd.arg("--").args(args);
+                }
+                Ok(cmd)
+            }
+            Launcher::Built(bins) => {
+                let mut cmd = Command::new(bins.executable(puzzle)?);
+                cmd.args(args);
+                Ok(cmd)
+            }
+        }
+    }
+}
+
+/// Wraps `cmd` so that the address space of the started process is capped at `megabytes`.
+/// Only supported on unix, where the limit is set with the `ulimit` builtin of `sh`.
+pub fn with_memory_limit(cmd: &Command, megabytes: u64) -> Command {
+    if !cfg!(unix) {
+        eprintln!("Memory limits are only supported on unix, running without one.");
+        let mut unlimited = Command::new(cmd.get_program());
+        unlimited.args(cmd.get_args());
+        return unlimited;
+    }
+
+    let mut limited = Command::new("sh");
+    limited
+        .arg("-c")
+        .arg(r#"ulimit -v "$1" && shift && exec "$@""#)
+        .arg("sh")
+        .arg((megabytes * 1024).to_string())
+        .arg(cmd.get_program())
+        .args(cmd.get_args());
+    limited
+}
+
+/// Executables produced by `build`, and the compiler errors of the bins that failed.
+#[derive(Clone, Debug, Default)]
+pub struct BuiltBins {
+    executables: HashMap<String, PathBuf>,
+    errors: HashMap<String, Vec<String>>,
+    /// Errors of the library, which every bin depends on.
+    lib_errors: Vec<String>,
+}
+
+impl BuiltBins {
+    pub fn executable(&self, puzzle: PuzzleId) -> Result<PathBuf, String> {
+        let name = puzzle.to_string();
+        if let Some(path) = self.executables.get(&name) {
+            return Ok(path.clone());
+        }
+
+        let errors: Vec<&str> = self
+            .lib_errors
+            .iter()
+            .chain(self.errors.get(&name).into_iter().flatten())
+            .map(String::as_str)
+            .collect();
+
+        if errors.is_empty() {
+            Err(format!("bin {name} was not built."))
+        } else {
+            Err(format!(
+                "bin {name} failed to compile:\n{}",
+                errors.join("\n")
+            ))
+        }
+    }
+
+    /// Collects executables and errors from the JSON messages of `cargo build`.
+    fn from_messages(lines: impl IntoIterator<Item = String>) -> Self {
+        let mut bins = BuiltBins::default();
+
+        for line in lines {
+            let Ok(json) = JsonValue::from_str(&line) else {
+                continue;
+            };
+            let Some(message) = json.get::<HashMap<String, JsonValue>>() else {
+                continue;
+            };
+
+            let target = message
+                .get("target")
+                .and_then(|t| t.get::<HashMap<String, JsonValue>>());
+            let Some(name) = get_str(target.and_then(|t| t.get("name"))) else {
+                continue;
+            };
+            let is_lib = target
+                .and_then(|t| t.get("kind"))
+                .and_then(|k| k.get::<Vec<JsonValue>>())
+                .is_some_and(|kinds| {
+                    kinds
+                        .iter()
+                        .any(|k| k.get::<String>().is_some_and(|k| k == "lib"))
+                });
+
+            match get_str(message.get("reason")).map(String::as_str) {
+                Some("compiler-artifact") if !is_lib => {
+                    if let Some(executable) = get_str(message.get("executable")) {
+                        bins.executables
+                            .insert(name.clone(), PathBuf::from(executable));
+                    }
+                }
+                Some("compiler-message") => {
+                    let diagnostic = message
+                        .get("message")
+                        .and_then(|m| m.get::<HashMap<String, JsonValue>>());
+                    let is_error = get_str(diagnostic.and_then(|d| d.get("level")))
+                        .is_some_and(|level| level == "error");
+                    let Some(rendered) = get_str(diagnostic.and_then(|d| d.get("rendered"))) else {
+                        continue;
+                    };
+
+                    if !is_error {
+                        continue;
+                    }
+                    if is_lib {
+                        bins.lib_errors.push(rendered.clone());
+                    } else {
+                        bins.errors
+                            .entry(name.clone())
+                            .or_default()
+                            .push(rendered.clone());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        bins
+    }
+}
+
+fn get_str(value: Option<&JsonValue>) -> Option<&String> {
+    value.and_then(|v| v.get::<String>())
+}
+
+/// Runs `cargo build` for all bins, or only the bin of `puzzle`, and keeps going past bins that
+/// do not compile.
+pub fn build(profile: Profile, puzzle: Option<PuzzleId>) -> Result<BuiltBins, io::Error> {
+    let mut cmd = Command::new("cargo");
+    cmd.args(["build", "--quiet", "--keep-going", "--message-format=json"])
+        .args(profile.cargo_args());
+
+    match puzzle {
+        Some(puzzle) => cmd.args(["--bin", &puzzle.to_string()]),
+        None => cmd.arg("--bins"),
+    };
+
+    let mut child = cmd
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()?;
+
+    let stdout = child.stdout.take().ok_or(io::ErrorKind::BrokenPipe)?;
+    let bins = BuiltBins::from_messages(BufReader::new(stdout).lines().map_while(Result::ok));
+    child.wait()?;
+
+    Ok(bins)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::BuiltBins;
+    use crate::day;
+
+    #[test]
+    fn collects_executables_and_errors() {
+        let messages = [
+            r#"{"reason":"compiler-artifact","target":{"kind":["lib"],"name":"advent_of_code"},"executable":null}"#,
+            r#"{"reason":"compiler-artifact","target":{"kind":["bin"],"name":"01"},"executable":"/target/release/01"}"#,
+            r#"{"reason":"compiler-message","target":{"kind":["bin"],"name":"02"},"message":{"level":"warning","rendered":"warning: unused"}}"#,
+            r#"{"reason":"compiler-message","target":{"kind":["bin"],"name":"02"},"message":{"level":"error","rendered":"error[E0308]: mismatched types"}}"#,
+            r#"{"reason":"build-finished","success":false}"#,
+            "not json",
+        ];
+        let bins = BuiltBins::from_messages(messages.iter().map(|m| m.to_string()));
+
+        assert_eq!(
+            bins.executable(day!(1).into()),
+            Ok(PathBuf::from("/target/release/01"))
+        );
+
+        let error = bins.executable(day!(2).into()).unwrap_err();
+        assert!(error.contains("mismatched types"));
+        assert!(!error.contains("unused"));
+
+        assert_eq!(
+            bins.executable(day!(3).into()),
+            Err("bin 03 was not built.".to_string())
+        );
+    }
+
+    #[test]
+    fn blames_lib_errors_on_every_bin() {
+        let messages = [
+            r#"{"reason":"compiler-message","target":{"kind":["lib"],"name":"advent_of_code"},"message":{"level":"error","rendered":"error: broken helper"}}"#,
+        ];
+        let bins = BuiltBins::from_messages(messages.iter().map(|m| m.to_string()));
+
+        assert!(
+            bins.executable(day!(1).into())
+                .unwrap_err()
+                .contains("broken helper")
+        );
+        assert!(
+            bins.executable(day!(9).into())
+                .unwrap_err()
+                .contains("broken helper")
+        );
+    }
+}
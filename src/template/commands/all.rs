@@ -1,5 +1,16 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::{
+    Year, all_days,
+    run_multi::{Limits, run_multi},
+};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(year: Option<Year>, is_release: bool, jobs: usize, build_once: bool, limits: Limits) {
+    run_multi(
+        year,
+        &all_days(year).collect(),
+        is_release,
+        false,
+        jobs,
+        build_once,
+        limits,
+    );
 }
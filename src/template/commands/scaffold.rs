@@ -1,15 +1,17 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::{PuzzleId, examples::Examples};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
+    create_parent_dir(path)?;
     let mut file = OpenOptions::new();
     if overwrite {
         file.create(true);
@@ -20,6 +22,7 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
+    create_parent_dir(path)?;
     OpenOptions::new()
         .write(true)
         .create(true)
@@ -27,10 +30,47 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Files that were downloaded or edited already are kept when scaffolding again.
+fn has_contents(path: &str) -> bool {
+    fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0)
+}
+
+/// Fill in the template, with the examples and answers of the puzzle description if it was
+/// downloaded already.
+fn module_contents(puzzle: PuzzleId, examples: Option<&Examples>) -> String {
+    let example = |part| match examples.and_then(|e| e.file_number(part)) {
+        Some(n) => format!("read_file_part(\"examples\", PUZZLE, {n})"),
+        None => "read_file(\"examples\", PUZZLE)".into(),
+    };
+    // the scaffolded solutions return `u64`, other answers are only noted for the tests.
+    let answer = |part| match examples.and_then(|e| e.answer(part)) {
+        Some(answer) if answer.parse::<u64>().is_ok() => format!("Some({answer})"),
+        Some(answer) => format!("None /* expected `{answer}` */"),
+        None => "None".into(),
+    };
+
+    MODULE_TEMPLATE
+        .replace("%DAY_NUMBER%", &puzzle.day.into_inner().to_string())
+        .replace("%PART_ONE_EXAMPLE%", &example(1))
+        .replace("%PART_ONE_ANSWER%", &answer(1))
+        .replace("%PART_TWO_EXAMPLE%", &example(2))
+        .replace("%PART_TWO_ANSWER%", &answer(2))
+}
+
+/// Years other than the workspace's own event keep their data in folders of their own.
+fn create_parent_dir(path: &str) -> Result<(), std::io::Error> {
+    match Path::new(path).parent() {
+        Some(parent) => fs::create_dir_all(parent),
+        None => Ok(()),
+    }
+}
+
+pub fn handle(puzzle: PuzzleId, overwrite: bool) {
+    let day = puzzle.day;
+    let input_path = puzzle.data_path("inputs", "txt").display().to_string();
+    let example_path = puzzle.data_path("examples", "txt").display().to_string();
+    let module_path = puzzle.bin_path();
+    let examples = Examples::read_from_file(puzzle);
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -40,13 +80,9 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(module_contents(puzzle, examples.as_ref()).as_bytes()) {
         Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+            println!("Created module file \"{}\"", module_path);
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
@@ -54,26 +90,35 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+    if has_contents(&input_path) {
+        println!("Kept downloaded input file \"{}\"", input_path);
+    } else {
+        match create_file(&input_path) {
+            Ok(_) => {
+                println!("Created empty input file \"{}\"", input_path);
+            }
+            Err(e) => {
+                eprintln!("Failed to create input file: {e}");
+                process::exit(1);
+            }
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+    if !has_contents(&example_path) {
+        match create_file(&example_path) {
+            Ok(_) => {
+                println!("Created empty example file \"{}\"", example_path);
+            }
+            Err(e) => {
+                eprintln!("Failed to create example file: {e}");
+                process::exit(1);
+            }
         }
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    match puzzle.year {
+        Some(year) => println!("🎄 Type `cargo solve {day} --year {year}` to run your solution."),
+        None => println!("🎄 Type `cargo solve {day}` to run your solution."),
+    }
 }
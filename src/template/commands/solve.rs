@@ -1,30 +1,57 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::PuzzleId;
+use crate::template::bins::{Launcher, Profile};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
-
-    if dhat {
-        cmd_args.extend([
-            "--profile".to_string(),
-            "dhat".to_string(),
-            "--features".to_string(),
-            "dhat-heap".to_string(),
-        ]);
+pub fn handle(
+    puzzle: PuzzleId,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    force: bool,
+    build_once: bool,
+) {
+    let profile = if dhat {
+        Profile::Dhat
     } else if release {
-        cmd_args.push("--release".to_string());
-    }
+        Profile::Release
+    } else {
+        Profile::Debug
+    };
 
-    cmd_args.push("--".to_string());
+    let mut bin_args: Vec<String> = vec![];
 
     if let Some(submit_part) = submit_part {
-        cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
+        bin_args.push("--submit".to_string());
+        bin_args.push(submit_part.to_string());
+
+        if force {
+            bin_args.push("--force".to_string());
+        }
     }
 
-    let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
+    let mut cmd = if build_once {
+        // build first, so that compiler output does not mix with the output of the solution.
+        let bin_args: Vec<&str> = bin_args.iter().map(String::as_str).collect();
+        match Launcher::built(profile, Some(puzzle)).command(puzzle, &bin_args) {
+            Ok(cmd) => cmd,
+            Err(build_error) => {
+                eprintln!("{build_error}");
+                process::exit(1);
+            }
+        }
+    } else {
+        let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.to_string()];
+        cmd_args.extend(profile.cargo_args().iter().map(ToString::to_string));
+        cmd_args.push("--".to_string());
+        cmd_args.extend(bin_args);
+
+        let mut cmd = Command::new("cargo");
+        cmd.args(&cmd_args);
+        cmd
+    };
+
+    let mut cmd = cmd
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
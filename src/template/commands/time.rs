@@ -1,19 +1,49 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, process};
 
-use crate::template::run_multi::run_multi;
+use crate::template::history::{self, History, HistoryOptions, HistoryRun};
+use crate::template::run_multi::{Limits, run_multi};
 use crate::template::timings::Timings;
-use crate::template::{Day, all_days, readme_benchmarks};
+use crate::template::{Day, Year, all_days, readme_benchmarks};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+pub fn handle(
+    year: Option<Year>,
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    build_once: bool,
+    limits: Limits,
+    options: HistoryOptions,
+) {
+    let stored_timings = match Timings::read_from_file(year) {
+        Ok(timings) => timings,
+        Err(e) => {
+            eprintln!("Failed to read stored timings: {e}");
+            process::exit(1);
+        }
+    };
+
+    // a named baseline implies that the run should be compared.
+    let compare = options.compare || options.baseline.is_some();
+    // read the history up front, so that a broken file is reported before benchmarking.
+    let mut history = if store || compare {
+        match History::read_from_file(year) {
+            Ok(history) => history,
+            Err(e) => {
+                eprintln!("Failed to read timing history: {e}");
+                process::exit(1);
+            }
+        }
+    } else {
+        History::default()
+    };
 
     let days_to_run = day.map_or_else(
         || {
             if run_all {
-                all_days().collect()
+                all_days(year).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
+                all_days(year)
                     .filter(|day| !stored_timings.is_day_complete(*day))
                     .collect()
             }
@@ -21,14 +51,14 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings = run_multi(year, &days_to_run, true, true, 1, build_once, limits).unwrap();
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings.store_file(year).unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(merged_timings, year) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -37,4 +67,35 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    let mut regressed = false;
+    let run = HistoryRun::from_timings(&timings, options.label);
+
+    if !run.days.is_empty() {
+        if compare {
+            match history.compare(&run, options.baseline.as_deref()) {
+                Ok(changes) => {
+                    regressed =
+                        history::report(&changes, options.baseline.as_deref(), options.threshold);
+                }
+                Err(e) => {
+                    eprintln!("Failed to compare timings: {e}");
+                    process::exit(1);
+                }
+            }
+        }
+
+        // only stored runs are recorded, so partial runs of single days don't end up as the
+        // previous run that later ones are compared with.
+        if store {
+            history.runs.push(run);
+            if history.store_file(year).is_err() {
+                eprintln!("Failed to store timing history.");
+            }
+        }
+    }
+
+    if regressed {
+        process::exit(1);
+    }
 }
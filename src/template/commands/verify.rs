@@ -0,0 +1,156 @@
+use std::{path::Path, process};
+
+use crate::template::answers::Answers;
+use crate::template::bins::{Launcher, Profile};
+use crate::template::protocol::Record;
+use crate::template::run_multi::{Limits, child_commands};
+use crate::template::timings::PartStatus;
+use crate::template::{ANSI_BOLD, ANSI_RESET, Day, PuzzleId, Year, all_days};
+
+/// Outcome of comparing a part with its stored answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Verdict {
+    Pass,
+    Fail,
+    Missing,
+}
+
+fn verdict(expected: Option<&str>, record: Option<&Record>) -> Verdict {
+    let Some(expected) = expected else {
+        return Verdict::Missing;
+    };
+
+    match record {
+        Some(record)
+            if record.status == PartStatus::Solved
+                && record.answer.as_deref() == Some(expected) =>
+        {
+            Verdict::Pass
+        }
+        _ => Verdict::Fail,
+    }
+}
+
+/// What a part produced, for failure messages.
+fn describe(record: Option<&Record>) -> String {
+    match record {
+        Some(Record {
+            answer: Some(answer),
+            ..
+        }) => format!("`{answer}`"),
+        Some(Record {
+            error: Some(error), ..
+        }) => format!("an error: {error}"),
+        _ => "no answer".into(),
+    }
+}
+
+pub fn handle(
+    year: Option<Year>,
+    day: Option<Day>,
+    is_release: bool,
+    build_once: bool,
+    limits: Limits,
+) {
+    let answers = match Answers::read_from_file(year) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("Failed to read stored answers: {e}");
+            process::exit(1);
+        }
+    };
+
+    let profile = if is_release {
+        Profile::Release
+    } else {
+        Profile::Debug
+    };
+    let launcher = if build_once || limits.is_limited() {
+        Launcher::built(profile, day.map(|day| PuzzleId { year, day }))
+    } else {
+        Launcher::Cargo(profile)
+    };
+
+    let days: Vec<Day> = match day {
+        Some(day) => vec![day],
+        None => all_days(year)
+            .filter(|&day| Path::new(&PuzzleId { year, day }.bin_path()).exists())
+            .collect(),
+    };
+
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+
+    for (i, day) in days.into_iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        let puzzle = PuzzleId { year, day };
+        let run = child_commands::run_solution(puzzle, &launcher, false, false, limits).unwrap();
+
+        for part in 1..=2 {
+            let record = run.records.iter().find(|r| r.part == Some(part));
+            let expected = answers.get(day, part);
+
+            match verdict(expected, record) {
+                Verdict::Pass => {
+                    passed += 1;
+                    println!("Verify {part}: ✔ pass");
+                }
+                Verdict::Fail => {
+                    failed += 1;
+                    println!(
+                        "Verify {part}: ✘ fail, expected `{}` but got {}",
+                        expected.unwrap_or_default(),
+                        describe(record)
+                    );
+                }
+                Verdict::Missing => {
+                    missing += 1;
+                    println!("Verify {part}: ? no stored answer");
+                }
+            }
+        }
+    }
+
+    println!(
+        "\n{ANSI_BOLD}Verify:{ANSI_RESET} {passed} passed, {failed} failed, {missing} missing"
+    );
+
+    if failed > 0 {
+        process::exit(1);
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Verdict, verdict};
+    use crate::template::{protocol::Record, runner::BenchStats, timings::PartStatus};
+
+    fn record(status: PartStatus, answer: Option<&str>) -> Record {
+        Record {
+            part: Some(1),
+            status,
+            answer: answer.map(Into::into),
+            error: None,
+            stats: BenchStats::from_durations(&[Duration::from_millis(1)]),
+        }
+    }
+
+    #[test]
+    fn compares_with_stored_answers() {
+        let solved = record(PartStatus::Solved, Some("42"));
+        assert_eq!(verdict(Some("42"), Some(&solved)), Verdict::Pass);
+        assert_eq!(verdict(Some("41"), Some(&solved)), Verdict::Fail);
+        assert_eq!(verdict(None, Some(&solved)), Verdict::Missing);
+
+        let unsolved = record(PartStatus::Unsolved, None);
+        assert_eq!(verdict(Some("42"), Some(&unsolved)), Verdict::Fail);
+        assert_eq!(verdict(Some("42"), None), Verdict::Fail);
+    }
+}
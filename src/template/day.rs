@@ -2,13 +2,16 @@ use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
 
+use crate::template::{Year, last_day};
+
 #[cfg(feature = "today")]
 use chrono::{Datelike, FixedOffset, Utc};
 
 #[cfg(feature = "today")]
 const SERVER_UTC_OFFSET: i32 = -5;
 
-/// A valid day number of advent (i.e. an integer in range 1 to 25).
+/// A valid day number of advent, i.e. an integer from 1 to the last day of the longest events, 25.
+/// Later events are shorter, see [`Year::max_day`] for the last day of a given year.
 ///
 /// # Display
 /// This value displays as a two digit number.
@@ -22,8 +25,8 @@ const SERVER_UTC_OFFSET: i32 = -5;
 pub struct Day(u8);
 
 impl Day {
-    /// Creates a [`Day`] from the provided value if it's in the valid range,
-    /// returns [`None`] otherwise.
+    /// Creates a [`Day`] from the provided value if it's a day of any event, returns [`None`]
+    /// otherwise. It may still be past the last day of a given year, see [`Year::max_day`].
     pub const fn new(day: u8) -> Option<Self> {
         if day == 0 || day > 25 {
             return None;
@@ -39,12 +42,15 @@ impl Day {
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    /// Returns the current day if it's between the 1st and the last day of this year's event in
+    /// december, `None` otherwise.
     pub fn today() -> Option<Self> {
         let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
         let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
-            Self::new(u8::try_from(today.day()).ok()?)
+        let year = Year::new(u16::try_from(today.year()).ok()?)?;
+        let day = Self::new(u8::try_from(today.day()).ok()?)?;
+        if today.month() == 12 && day <= year.max_day() {
+            Some(day)
         } else {
             None
         }
@@ -88,26 +94,30 @@ impl Error for DayFromStrError {}
 
 impl Display for DayFromStrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("expecting a day number between 1 and 25")
+        f.write_str("expecting a day number between 1 and 25, the most days of any event")
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
-pub fn all_days() -> AllDays {
-    AllDays::new()
+/// An iterator that yields every day of the event of `year` from the 1st to its last day,
+/// see [`last_day`].
+pub fn all_days(year: Option<Year>) -> AllDays {
+    AllDays::new(last_day(year))
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of advent from the 1st to the given last day.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
-    #[allow(clippy::new_without_default)]
-    pub fn new() -> Self {
-        Self { current: 1 }
+    pub fn new(last: Day) -> Self {
+        Self {
+            current: 1,
+            last: last.0,
+        }
     }
 }
 
@@ -115,10 +125,11 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and we have verified that the value is not above the
+        // last day, which is a valid day itself.
         let day = Day(self.current);
         self.current += 1;
 
@@ -134,7 +145,7 @@ macro_rules! day {
     ($day:expr) => {
         const {
             $crate::template::Day::new($day)
-                .expect("invalid day number, expecting a value between 1 and 25")
+                .expect("invalid day number, no event has a day outside 1 to 25")
         }
     };
 }
@@ -143,11 +154,11 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{Day, all_days};
+    use super::{AllDays, Day};
 
     #[test]
     fn all_days_iterator() {
-        let mut iter = all_days();
+        let mut iter = AllDays::new(Day(25));
 
         assert_eq!(iter.next(), Some(Day(1)));
         assert_eq!(iter.next(), Some(Day(2)));
@@ -176,6 +187,13 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn all_days_iterator_stops_at_last_day() {
+        let days: Vec<Day> = AllDays::new(Day(12)).collect();
+        assert_eq!(days.len(), 12);
+        assert_eq!(days.last(), Some(&Day(12)));
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -0,0 +1,166 @@
+/// Bookkeeping of requests to the puzzle site, used to keep a minimum interval between them.
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    path::PathBuf,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::data_dir;
+
+static DOWNLOAD_STATE_FILE_NAME: &str = "download_state.json";
+
+/// Minimum time between two requests to the puzzle site.
+pub const MIN_REQUEST_INTERVAL: Duration = Duration::from_secs(3);
+
+/// State shared by all events, since the interval applies to the whole site.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DownloadState {
+    /// Milliseconds since the unix epoch.
+    pub last_request: Option<u64>,
+}
+
+impl DownloadState {
+    /// Dehydrate the state to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the state from a JSON file. If not present, no request was made yet.
+    /// A file that can't be read is reported and treated the same, it is replaced by the next
+    /// request.
+    pub fn read_from_file() -> Self {
+        match fs::read_to_string(Self::path()) {
+            Ok(json) => DownloadState::try_from(json).unwrap_or_else(|e| {
+                eprintln!("Ignoring invalid download state: {e}");
+                DownloadState::default()
+            }),
+            Err(e) if e.kind() == ErrorKind::NotFound => DownloadState::default(),
+            Err(e) => {
+                eprintln!("Ignoring unreadable download state: {e}");
+                DownloadState::default()
+            }
+        }
+    }
+
+    fn path() -> PathBuf {
+        data_dir(None).join(DOWNLOAD_STATE_FILE_NAME)
+    }
+
+    /// Remaining time until the next request may be made, `None` if it may be made right away.
+    pub fn wait_time(&self, interval: Duration) -> Option<Duration> {
+        let elapsed = now_millis().saturating_sub(self.last_request?);
+        interval
+            .checked_sub(Duration::from_millis(elapsed))
+            .filter(|wait| !wait.is_zero())
+    }
+
+    /// Note a request made just now.
+    pub fn record(&mut self) {
+        self.last_request = Some(now_millis());
+    }
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX))
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<DownloadState> for JsonValue {
+    fn from(value: DownloadState) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "last_request".into(),
+            value
+                .last_request
+                .map_or(JsonValue::Null, |x| JsonValue::Number(x as f64)),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for DownloadState {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let last_request = match json.get("last_request") {
+            None | Some(JsonValue::Null) => None,
+            Some(value) => Some(
+                value
+                    .get::<f64>()
+                    .map(|&x| x as u64)
+                    .ok_or("expected `json.last_request` to be a number.")?,
+            ),
+        };
+
+        Ok(DownloadState { last_request })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use tinyjson::JsonValue;
+
+    use super::{DownloadState, now_millis};
+
+    #[test]
+    fn waits_for_the_interval() {
+        let interval = Duration::from_secs(3);
+        assert_eq!(DownloadState::default().wait_time(interval), None);
+
+        let recent = DownloadState {
+            last_request: Some(now_millis() - 1000),
+        };
+        let wait = recent.wait_time(interval).unwrap();
+        assert!(wait <= Duration::from_secs(2) && wait > Duration::from_secs(1));
+
+        let old = DownloadState {
+            last_request: Some(now_millis() - 5000),
+        };
+        assert_eq!(old.wait_time(interval), None);
+    }
+
+    #[test]
+    fn handles_json_state() {
+        let mut state = DownloadState::default();
+        state.record();
+
+        let json = JsonValue::from(state.clone()).stringify().unwrap();
+        assert_eq!(DownloadState::try_from(json).unwrap(), state);
+
+        let json = JsonValue::from(DownloadState::default())
+            .stringify()
+            .unwrap();
+        assert_eq!(
+            DownloadState::try_from(json).unwrap(),
+            DownloadState::default()
+        );
+    }
+}
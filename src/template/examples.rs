@@ -0,0 +1,315 @@
+/// Extracts the examples and their expected answers from downloaded puzzle descriptions, so that
+/// they don't have to be copied by hand.
+use std::{fs, io::Error, path::PathBuf};
+
+use crate::template::{PuzzleId, aoc_client::PART_TWO_HEADING, data_dir};
+
+/// An example input and the part whose description introduces it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Example {
+    pub part: u8,
+    pub input: String,
+}
+
+/// Examples of a puzzle, in the order they appear in its description.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Examples {
+    pub examples: Vec<Example>,
+    /// The emphasised value in the prose following the example of each part, usually the
+    /// expected answer. `None` if there is no such value, or several different ones.
+    pub answers: [Option<String>; 2],
+    /// The index in `examples` of the example each part's answer is taken from, which may be
+    /// introduced by the other part if it is repeated.
+    pub sources: [Option<usize>; 2],
+}
+
+/// Emphasised values in the prose following an example block, up to the next one.
+struct Candidates {
+    part: u8,
+    is_new: bool,
+    example: usize,
+    values: Vec<String>,
+}
+
+impl Examples {
+    /// Extract the examples from the Markdown of a puzzle description. Code blocks count as an
+    /// example if the text since the previous block says "For example", repeated inputs are
+    /// skipped. The answer of a part is taken from the prose after its first new example, or
+    /// after a repeated one if the part has none.
+    pub fn extract(markdown: &str) -> Self {
+        let mut extracted = Examples::default();
+        let mut part = 1;
+        let mut prose = String::new();
+        let mut block: Option<String> = None;
+        let mut candidates: Vec<Candidates> = vec![];
+
+        for line in markdown.lines() {
+            if line.starts_with("```") {
+                match block.take() {
+                    Some(input) => {
+                        let is_example = prose.to_lowercase().contains("for example");
+                        let repeated = extracted.examples.iter().position(|e| e.input == input);
+                        let is_new = repeated.is_none();
+                        if is_example && !input.trim().is_empty() {
+                            candidates.push(Candidates {
+                                part,
+                                is_new,
+                                example: repeated.unwrap_or(extracted.examples.len()),
+                                values: vec![],
+                            });
+                            if is_new {
+                                extracted.examples.push(Example { part, input });
+                            }
+                        }
+                        prose.clear();
+                    }
+                    None => block = Some(String::new()),
+                }
+                continue;
+            }
+
+            if let Some(input) = &mut block {
+                input.push_str(line);
+                input.push('\n');
+                continue;
+            }
+
+            if line.starts_with("## ") && line.contains(PART_TWO_HEADING) {
+                part = 2;
+                prose.clear();
+                continue;
+            }
+
+            if let Some(last) = candidates.last_mut().filter(|c| c.part == part) {
+                last.values.extend(emphasised_values(line));
+            }
+            prose.push_str(line);
+            prose.push('\n');
+        }
+
+        for (part, (answer, source)) in
+            (1..).zip(extracted.answers.iter_mut().zip(&mut extracted.sources))
+        {
+            let mut in_part = candidates.iter().filter(|c| c.part == part);
+            let chosen = in_part
+                .clone()
+                .find(|c| c.is_new)
+                .or_else(|| in_part.next());
+            *source = chosen.map(|c| c.example);
+            *answer = chosen.and_then(|c| {
+                let first = c.values.first()?;
+                c.values.iter().all(|v| v == first).then(|| first.clone())
+            });
+        }
+
+        extracted
+    }
+
+    /// Extract the examples from the downloaded description of `puzzle`, if there is one.
+    pub fn read_from_file(puzzle: PuzzleId) -> Option<Self> {
+        fs::read_to_string(puzzle.data_path("puzzles", "md"))
+            .ok()
+            .map(|markdown| Examples::extract(&markdown))
+    }
+
+    /// The expected answer of `part` as written in the description, e.g. `42`, `-3` or `ABC`.
+    pub fn answer(&self, part: u8) -> Option<&str> {
+        self.answers[usize::from(part - 1)].as_deref()
+    }
+
+    /// The number of the file that holds the example the answer of `part` is taken from, as
+    /// passed to `read_file_part`. `None` if that is the first example of the puzzle.
+    pub fn file_number(&self, part: u8) -> Option<usize> {
+        self.sources[usize::from(part - 1)]
+            .filter(|&i| i > 0)
+            .map(|i| i + 1)
+    }
+
+    /// Extract the examples from the downloaded description of `puzzle` into its example files,
+    /// and report the files that were written.
+    pub fn store_from_description(puzzle: PuzzleId) {
+        let Some(examples) = Examples::read_from_file(puzzle) else {
+            return;
+        };
+        match examples.store_files(puzzle) {
+            Ok(paths) => {
+                for path in paths {
+                    println!("🎄 Extracted example to \"{}\".", path.display());
+                }
+            }
+            Err(e) => eprintln!("Failed to write example file: {e}"),
+        }
+    }
+
+    /// Write the examples to `{day}.txt` and, if there are several, to `{day}-{n}.txt`.
+    /// Files that already have contents are left alone. Returns the paths that were written.
+    pub fn store_files(&self, puzzle: PuzzleId) -> Result<Vec<PathBuf>, Error> {
+        let folder = data_dir(puzzle.year).join("examples");
+        let mut files = vec![];
+
+        if let Some(first) = self.examples.first() {
+            files.push((folder.join(format!("{}.txt", puzzle.day)), first));
+        }
+        if self.examples.len() > 1 {
+            for (i, example) in self.examples.iter().enumerate() {
+                files.push((
+                    folder.join(format!("{}-{}.txt", puzzle.day, i + 1)),
+                    example,
+                ));
+            }
+        }
+
+        let mut written = vec![];
+        for (path, example) in files {
+            let is_empty = fs::read_to_string(&path).map_or(true, |s| s.trim().is_empty());
+            if is_empty {
+                fs::create_dir_all(&folder)?;
+                fs::write(&path, &example.input)?;
+                written.push(path);
+            }
+        }
+
+        Ok(written)
+    }
+}
+
+/// Values that are emphasised inside inline code, i.e. `*42*` or *`42`*.
+fn emphasised_values(line: &str) -> Vec<String> {
+    let mut values = vec![];
+    let mut rest = line;
+
+    while let Some(start) = rest.find(['`', '*']) {
+        let (open, close) = match &rest[start..] {
+            s if s.starts_with("`*") => ("`*", "*`"),
+            s if s.starts_with("*`") => ("*`", "`*"),
+            _ => {
+                rest = &rest[start + 1..];
+                continue;
+            }
+        };
+
+        let inner = &rest[start + open.len()..];
+        let Some(end) = inner.find(close) else {
+            break;
+        };
+        values.push(inner[..end].to_string());
+        rest = &inner[end + close.len()..];
+    }
+
+    values
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Example, Examples, emphasised_values};
+
+    const PUZZLE_MARKDOWN: &str = "## --- Day 1: Test ---
+
+The elves keep lists of numbers.
+
+For example:
+
+```
+1
+2
+```
+
+After the first step:
+
+```
+3
+```
+
+In this example, the sum is `*3*`, but *not* `4`.
+
+## --- Part Two ---
+
+For example, using the same list:
+
+```
+1
+2
+```
+
+Now also consider this list, for example:
+
+```
+5
+6
+```
+
+The product of both lists is *`60`*.";
+
+    #[test]
+    fn finds_emphasised_values() {
+        assert_eq!(
+            emphasised_values("is `*3*`, but *not* `4`, or *`5`*"),
+            vec!["3", "5"]
+        );
+        assert!(emphasised_values("no `*answer` here").is_empty());
+    }
+
+    #[test]
+    fn extracts_examples_and_answers() {
+        let examples = Examples::extract(PUZZLE_MARKDOWN);
+
+        assert_eq!(
+            examples.examples,
+            vec![
+                Example {
+                    part: 1,
+                    input: "1\n2\n".into()
+                },
+                Example {
+                    part: 2,
+                    input: "5\n6\n".into()
+                },
+            ]
+        );
+        assert_eq!(examples.answer(1), Some("3"));
+        assert_eq!(examples.answer(2), Some("60"));
+        assert_eq!(examples.file_number(1), None);
+        assert_eq!(examples.file_number(2), Some(2));
+    }
+
+    #[test]
+    fn takes_answers_following_the_chosen_example() {
+        let examples = Examples::extract(
+            "For example:\n\n```\n1\n```\n\nThis gives *`-3`*.\n\n\
+            For example, another list:\n\n```\n2\n```\n\nThis gives *`7`*.\n\n\
+            ## --- Part Two ---\n\n\
+            For example:\n\n```\n3\n```\n\nThere are *`2`* paths of length *`5`*.",
+        );
+
+        assert_eq!(examples.examples.len(), 3);
+        assert_eq!(examples.answer(1), Some("-3"));
+        assert_eq!(examples.answer(2), None);
+    }
+
+    #[test]
+    fn numbers_the_file_of_a_repeated_example() {
+        let examples = Examples::extract(
+            "For example:\n\n```\n1\n```\n\nThis gives *`1`*.\n\n\
+            For example, another list:\n\n```\n2\n```\n\nThis gives *`2`*.\n\n\
+            ## --- Part Two ---\n\n\
+            For example, with the second list:\n\n```\n2\n```\n\nThis now gives *`4`*.",
+        );
+
+        assert_eq!(examples.examples.len(), 2);
+        assert_eq!(examples.answer(1), Some("1"));
+        assert_eq!(examples.file_number(1), None);
+        assert_eq!(examples.answer(2), Some("4"));
+        assert_eq!(examples.file_number(2), Some(2));
+    }
+
+    #[test]
+    fn handles_missing_examples() {
+        let examples = Examples::extract("## --- Day 1: Test ---\n\nNothing to see.");
+
+        assert!(examples.examples.is_empty());
+        assert_eq!(examples.answer(1), None);
+        assert_eq!(examples.file_number(2), None);
+    }
+}
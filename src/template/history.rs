@@ -0,0 +1,503 @@
+/// History of all timing runs, used to detect performance regressions between runs.
+use std::{
+    collections::HashMap,
+    env, fs,
+    io::{Error, ErrorKind},
+    process::Command,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{ANSI_BOLD, ANSI_RESET, Day, Year, data_dir, timings::Timings};
+
+static HISTORY_FILE_NAME: &str = "timings_history.json";
+
+/// Environment variable naming the machine a run was made on. Defaults to the hostname.
+pub const MACHINE_ENV: &str = "AOC_MACHINE";
+
+/// How `cargo time` records and compares runs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistoryOptions {
+    /// Name to tag the new run with, so that it can be used as a baseline later on. Runs are only
+    /// recorded together with `--store`.
+    pub label: Option<String>,
+    /// Whether to compare the new run with a previous one.
+    pub compare: bool,
+    /// Label of the run to compare with. Defaults to the previous run on the same machine.
+    pub baseline: Option<String>,
+    /// Relative change in percent above which a part counts as a regression or speedup.
+    pub threshold: f64,
+}
+
+impl Default for HistoryOptions {
+    fn default() -> Self {
+        Self {
+            label: None,
+            compare: false,
+            baseline: None,
+            threshold: 10.0,
+        }
+    }
+}
+
+/// Median nanoseconds of the parts of a day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DayNanos {
+    pub day: Day,
+    pub part_1: Option<f64>,
+    pub part_2: Option<f64>,
+}
+
+impl DayNanos {
+    fn part(&self, part: u8) -> Option<f64> {
+        match part {
+            1 => self.part_1,
+            2 => self.part_2,
+            _ => None,
+        }
+    }
+}
+
+/// A single `cargo time` run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistoryRun {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Short hash of the git commit the run was made on, if available.
+    pub commit: Option<String>,
+    pub machine: Option<String>,
+    pub label: Option<String>,
+    pub days: Vec<DayNanos>,
+}
+
+impl HistoryRun {
+    /// Capture the timings of a run made just now.
+    pub fn from_timings(timings: &Timings, label: Option<String>) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        HistoryRun {
+            timestamp,
+            commit: git_commit(),
+            machine: machine_label(),
+            label,
+            days: timings
+                .data
+                .iter()
+                .map(|t| DayNanos {
+                    day: t.day,
+                    part_1: t.part_1_nanos,
+                    part_2: t.part_2_nanos,
+                })
+                .filter(|d| d.part_1.is_some() || d.part_2.is_some())
+                .collect(),
+        }
+    }
+
+    fn nanos(&self, day: Day, part: u8) -> Option<f64> {
+        self.days.iter().find(|d| d.day == day)?.part(part)
+    }
+}
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    let output = String::from_utf8(output.stdout).ok()?;
+    let output = output.trim();
+    (!output.is_empty()).then(|| output.to_string())
+}
+
+fn git_commit() -> Option<String> {
+    command_output("git", &["rev-parse", "--short", "HEAD"])
+}
+
+fn machine_label() -> Option<String> {
+    env::var(MACHINE_ENV)
+        .ok()
+        .filter(|m| !m.is_empty())
+        .or_else(|| command_output("hostname", &[]))
+}
+
+/// A part that was timed both in the baseline and in the new run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change {
+    pub day: Day,
+    pub part: u8,
+    pub before: f64,
+    pub after: f64,
+}
+
+impl Change {
+    /// Relative change in percent, positive if the part got slower.
+    pub fn percent(&self) -> f64 {
+        (self.after - self.before) / self.before * 100.0
+    }
+
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.percent() > threshold
+    }
+
+    pub fn is_speedup(&self, threshold: f64) -> bool {
+        self.percent() < -threshold
+    }
+}
+
+/// All timing runs, oldest first.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub runs: Vec<HistoryRun>,
+}
+
+impl History {
+    /// Dehydrate the history to a JSON file.
+    pub fn store_file(&self, year: Option<Year>) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(data_dir(year).join(HISTORY_FILE_NAME))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the history from a JSON file. If not present, returns an empty history.
+    /// Fails if the file can't be read or parsed, so that it isn't replaced by an empty one.
+    pub fn read_from_file(year: Option<Year>) -> Result<Self, String> {
+        match fs::read_to_string(data_dir(year).join(HISTORY_FILE_NAME)) {
+            Ok(json) => History::try_from(json),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(History::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Compare `current` with the latest run tagged `baseline`, or with the latest previous
+    /// timing of each part on the same machine. Fails if the baseline does not exist.
+    pub fn compare(
+        &self,
+        current: &HistoryRun,
+        baseline: Option<&str>,
+    ) -> Result<Vec<Change>, String> {
+        let candidates: Vec<&HistoryRun> = match baseline {
+            Some(label) => {
+                let run = self
+                    .runs
+                    .iter()
+                    .rev()
+                    .find(|r| r.label.as_deref() == Some(label))
+                    .ok_or(format!("no run is labelled `{label}`."))?;
+                vec![run]
+            }
+            None => self
+                .runs
+                .iter()
+                .rev()
+                .filter(|r| r.machine == current.machine)
+                .collect(),
+        };
+
+        let mut changes = vec![];
+        for day in &current.days {
+            for part in [1, 2] {
+                let Some(after) = day.part(part) else {
+                    continue;
+                };
+                let before = candidates.iter().find_map(|r| r.nanos(day.day, part));
+                if let Some(before) = before.filter(|&b| b > 0.0) {
+                    changes.push(Change {
+                        day: day.day,
+                        part,
+                        before,
+                        after,
+                    });
+                }
+            }
+        }
+
+        Ok(changes)
+    }
+}
+
+fn format_nanos(nanos: f64) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = Duration::from_nanos(nanos as u64);
+    format!("{duration:.1?}")
+}
+
+/// Print the changes and return whether any part regressed by more than `threshold` percent.
+pub fn report(changes: &[Change], baseline: Option<&str>, threshold: f64) -> bool {
+    let against = baseline.map_or("the previous run".into(), |label| format!("`{label}`"));
+    println!("\n{ANSI_BOLD}Compared to {against}{ANSI_RESET} (threshold {threshold}%):");
+
+    if changes.is_empty() {
+        println!("No parts to compare.");
+        return false;
+    }
+
+    for change in changes {
+        let flag = if change.is_regression(threshold) {
+            " ▲ regression"
+        } else if change.is_speedup(threshold) {
+            " ▼ speedup"
+        } else {
+            ""
+        };
+        println!(
+            "Day {} part {}: {} → {} ({:+.1}%){flag}",
+            change.day,
+            change.part,
+            format_nanos(change.before),
+            format_nanos(change.after),
+            change.percent()
+        );
+    }
+
+    let regressions = changes
+        .iter()
+        .filter(|c| c.is_regression(threshold))
+        .count();
+    let speedups = changes.iter().filter(|c| c.is_speedup(threshold)).count();
+    println!("{regressions} regressions, {speedups} speedups.");
+
+    regressions > 0
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<History> for JsonValue {
+    fn from(value: History) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "runs".into(),
+            JsonValue::Array(value.runs.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for History {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_runs = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("runs")
+            .ok_or("expected JSON document to have key `runs`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.runs` to be an array.")?;
+
+        Ok(History {
+            runs: json_runs
+                .iter()
+                .map(HistoryRun::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn optional_to_json<T>(value: Option<T>, to_json: impl Fn(T) -> JsonValue) -> JsonValue {
+    value.map_or(JsonValue::Null, to_json)
+}
+
+impl From<&HistoryRun> for JsonValue {
+    fn from(value: &HistoryRun) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "commit".into(),
+            optional_to_json(value.commit.clone(), JsonValue::String),
+        );
+        map.insert(
+            "machine".into(),
+            optional_to_json(value.machine.clone(), JsonValue::String),
+        );
+        map.insert(
+            "label".into(),
+            optional_to_json(value.label.clone(), JsonValue::String),
+        );
+
+        let days = value
+            .days
+            .iter()
+            .map(|d| {
+                let mut day: HashMap<String, JsonValue> = HashMap::new();
+                day.insert("day".into(), JsonValue::String(d.day.to_string()));
+                day.insert(
+                    "part_1_nanos".into(),
+                    optional_to_json(d.part_1, JsonValue::Number),
+                );
+                day.insert(
+                    "part_2_nanos".into(),
+                    optional_to_json(d.part_2, JsonValue::Number),
+                );
+                JsonValue::Object(day)
+            })
+            .collect();
+        map.insert("days".into(), JsonValue::Array(days));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryRun {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected run to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|&x| x as u64)
+            .ok_or("Expected run.timestamp to be a number.")?;
+
+        let optional_string = |key: &str| match json.get(key) {
+            Some(v) if !v.is_null() => v
+                .get::<String>()
+                .cloned()
+                .map(Some)
+                .ok_or(format!("Expected run.{key} to be null or string.")),
+            _ => Ok(None),
+        };
+
+        let days = json
+            .get("days")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected run.days to be an array.")?
+            .iter()
+            .map(|d| {
+                let d = d
+                    .get::<HashMap<String, JsonValue>>()
+                    .ok_or("Expected run.days to contain objects.")?;
+
+                let day = d
+                    .get("day")
+                    .and_then(|v| v.get::<String>())
+                    .and_then(|day| Day::from_str(day).ok())
+                    .ok_or("Expected day.day to be a Day struct.")?;
+
+                let nanos = |key: &str| match d.get(key) {
+                    Some(v) if !v.is_null() => v
+                        .get::<f64>()
+                        .copied()
+                        .map(Some)
+                        .ok_or(format!("Expected day.{key} to be null or a number.")),
+                    _ => Ok(None),
+                };
+
+                Ok(DayNanos {
+                    day,
+                    part_1: nanos("part_1_nanos")?,
+                    part_2: nanos("part_2_nanos")?,
+                })
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(HistoryRun {
+            timestamp,
+            commit: optional_string("commit")?,
+            machine: optional_string("machine")?,
+            label: optional_string("label")?,
+            days,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{DayNanos, History, HistoryRun};
+    use crate::day;
+
+    fn run(
+        machine: &str,
+        label: Option<&str>,
+        days: &[(u8, Option<f64>, Option<f64>)],
+    ) -> HistoryRun {
+        HistoryRun {
+            timestamp: 1_700_000_000,
+            commit: Some("abc1234".into()),
+            machine: Some(machine.into()),
+            label: label.map(Into::into),
+            days: days
+                .iter()
+                .map(|&(day, part_1, part_2)| DayNanos {
+                    day: crate::template::Day::new(day).unwrap(),
+                    part_1,
+                    part_2,
+                })
+                .collect(),
+        }
+    }
+
+    fn get_mock_history() -> History {
+        History {
+            runs: vec![
+                run(
+                    "laptop",
+                    Some("v1"),
+                    &[(1, Some(100.0), Some(200.0)), (2, Some(50.0), None)],
+                ),
+                run("desktop", None, &[(1, Some(10.0), Some(20.0))]),
+                run("laptop", None, &[(1, Some(120.0), None)]),
+            ],
+        }
+    }
+
+    #[test]
+    fn compares_with_previous_timings_of_same_machine() {
+        let history = get_mock_history();
+        let current = run(
+            "laptop",
+            None,
+            &[(1, Some(60.0), Some(250.0)), (3, Some(1.0), None)],
+        );
+        let changes = history.compare(&current, None).unwrap();
+
+        assert_eq!(changes.len(), 2);
+        assert_eq!((changes[0].day, changes[0].part), (day!(1), 1));
+        assert_eq!(changes[0].before, 120.0);
+        assert_eq!(changes[0].percent(), -50.0);
+        assert!(changes[0].is_speedup(10.0));
+        // part 2 was not timed in the latest run, fall back to the one before.
+        assert_eq!(changes[1].before, 200.0);
+        assert!(changes[1].is_regression(10.0));
+        assert!(!changes[1].is_regression(30.0));
+    }
+
+    #[test]
+    fn compares_with_named_baseline() {
+        let history = get_mock_history();
+        let current = run("desktop", None, &[(2, Some(55.0), None)]);
+
+        let changes = history.compare(&current, Some("v1")).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].before, 50.0);
+        assert!(!changes[0].is_regression(10.0));
+
+        assert!(history.compare(&current, Some("v2")).is_err());
+    }
+
+    #[test]
+    fn roundtrips_history() {
+        let history = get_mock_history();
+        let json = JsonValue::from(history.clone()).stringify().unwrap();
+        let roundtrip = History::try_from(json).unwrap();
+        assert_eq!(roundtrip.runs, history.runs);
+    }
+}
@@ -1,15 +1,26 @@
 use std::{env, fs};
 
-pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
 pub mod runner;
 
 pub use day::*;
+pub use history::HistoryOptions;
+pub use run_multi::Limits;
+pub use year::*;
 
+mod answers;
+mod bins;
 mod day;
+mod download_state;
+mod examples;
+mod history;
+mod protocol;
 mod readme_benchmarks;
 mod run_multi;
+mod submissions;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
@@ -17,28 +28,33 @@ pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, puzzle: impl Into<PuzzleId>) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd.join(puzzle.into().data_path(folder, "txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, puzzle: impl Into<PuzzleId>, part: u8) -> String {
+    let puzzle = puzzle.into();
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
+        .join(data_dir(puzzle.year))
         .join(folder)
-        .join(format!("{day}-{part}.txt"));
+        .join(format!("{}-{part}.txt", puzzle.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `DAY` and `PUZZLE` and sets up the input and runner for each part.
+/// The year of `PUZZLE` is taken from the bin name, e.g. `2024-05`.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// With a trailing `parse = <fn>`, the input is parsed once by `<fn>` (timed separately) and both
+/// parts receive a reference to the parsed value instead of the raw input.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -50,19 +66,46 @@ macro_rules! solution {
     ($day:expr, 2) => {
         $crate::solution!(@impl $day, [part_two, 2]);
     };
+    ($day:expr, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, parse = $parse, [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, 1, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, parse = $parse, [part_one, 1]);
+    };
+    ($day:expr, 2, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, parse = $parse, [part_two, 2]);
+    };
 
     (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@header $day);
+
+        fn main() {
+            use $crate::template::runner::*;
+            let input = $crate::template::read_file("inputs", PUZZLE);
+            $( run_part($func, &input, PUZZLE, $part); )*
+        }
+    };
+    (@impl $day:expr, parse = $parse:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@header $day);
+
+        fn main() {
+            use $crate::template::runner::*;
+            let input = $crate::template::read_file("inputs", PUZZLE);
+            let parsed = run_parse($parse, &input);
+            $( run_part($func, &parsed, PUZZLE, $part); )*
+        }
+    };
+
+    (@header $day:expr) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
+        /// The current puzzle, scoped to a year if the bin is named like `2024-05`.
+        const PUZZLE: $crate::template::PuzzleId =
+            $crate::template::PuzzleId::from_bin_name(env!("CARGO_BIN_NAME"), DAY);
+
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
-
-        fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
-        }
     };
 }
@@ -0,0 +1,209 @@
+/// Structured results that solution binaries report to `run_multi`, one JSON object per line.
+///
+/// The binaries keep printing their human-readable output. Records are written to stdout as well,
+/// prefixed with `RECORD_PREFIX` so they can be told apart from answers that span multiple lines.
+use std::{collections::HashMap, env, str::FromStr, time::Duration};
+
+use tinyjson::JsonValue;
+
+use super::{runner::BenchStats, timings::PartStatus};
+
+/// Environment variable that switches solution binaries to emit result records.
+pub const RESULT_FORMAT_ENV: &str = "AOC_RESULT_FORMAT";
+
+/// Value of `RESULT_FORMAT_ENV` that enables JSON lines.
+pub const JSON_LINES: &str = "jsonl";
+
+/// First character of every record line (ASCII record separator).
+pub const RECORD_PREFIX: char = '\u{1e}';
+
+/// Whether the current binary was asked to emit result records.
+pub fn is_enabled() -> bool {
+    env::var(RESULT_FORMAT_ENV).is_ok_and(|v| v == JSON_LINES)
+}
+
+/// Result of running the shared parser or a single part of a solution.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Record {
+    /// `None` for the shared input parser.
+    pub part: Option<u8>,
+    pub status: PartStatus,
+    pub answer: Option<String>,
+    /// Message of a failed part.
+    pub error: Option<String>,
+    pub stats: BenchStats,
+}
+
+impl Record {
+    /// The record as a single line, including `RECORD_PREFIX`.
+    pub fn to_line(&self) -> String {
+        let json = JsonValue::from(self).stringify().unwrap();
+        format!("{RECORD_PREFIX}{json}")
+    }
+
+    /// Parses a line written by `to_line`. Returns `None` if the line is not a record.
+    pub fn from_line(line: &str) -> Option<Result<Self, String>> {
+        let json = line.strip_prefix(RECORD_PREFIX)?;
+        Some(
+            JsonValue::from_str(json)
+                .map_err(|_| "record is not valid JSON.".to_string())
+                .and_then(|value| Record::try_from(&value)),
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[allow(clippy::cast_precision_loss)]
+fn nanos_to_json(duration: Duration) -> JsonValue {
+    JsonValue::Number(duration.as_nanos() as f64)
+}
+
+fn optional_string_to_json(value: Option<&String>) -> JsonValue {
+    match value {
+        Some(x) => JsonValue::String(x.clone()),
+        None => JsonValue::Null,
+    }
+}
+
+impl From<&Record> for JsonValue {
+    fn from(value: &Record) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "part".into(),
+            match value.part {
+                Some(part) => JsonValue::Number(f64::from(part)),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "status".into(),
+            JsonValue::String(value.status.as_str().into()),
+        );
+        map.insert(
+            "answer".into(),
+            optional_string_to_json(value.answer.as_ref()),
+        );
+        map.insert(
+            "error".into(),
+            optional_string_to_json(value.error.as_ref()),
+        );
+
+        let stats = &value.stats;
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(stats.samples as f64));
+        map.insert("nanos".into(), nanos_to_json(stats.median));
+        map.insert("min_nanos".into(), nanos_to_json(stats.min));
+        map.insert("p95_nanos".into(), nanos_to_json(stats.p95));
+        map.insert("max_nanos".into(), nanos_to_json(stats.max));
+        map.insert("stddev_nanos".into(), nanos_to_json(stats.stddev));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("outliers".into(), JsonValue::Number(stats.outliers as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Record {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected record to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|&x| x as u64)
+                .ok_or(format!("Expected record.{key} to be a number."))
+        };
+
+        let optional_string = |key: &str| match json.get(key) {
+            Some(v) if !v.is_null() => v
+                .get::<String>()
+                .cloned()
+                .map(Some)
+                .ok_or(format!("Expected record.{key} to be null or string.")),
+            _ => Ok(None),
+        };
+
+        let part = match json.get("part") {
+            Some(v) if !v.is_null() => Some(
+                u8::try_from(number("part")?).map_err(|_| "Expected record.part to be 1 or 2.")?,
+            ),
+            _ => None,
+        };
+
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected record.status to be a string.")
+            .and_then(|s| PartStatus::from_str(s).map_err(|_| "Unknown record.status."))?;
+
+        #[allow(clippy::cast_possible_truncation)]
+        let stats = BenchStats {
+            samples: u128::from(number("samples")?),
+            median: Duration::from_nanos(number("nanos")?),
+            min: Duration::from_nanos(number("min_nanos")?),
+            max: Duration::from_nanos(number("max_nanos")?),
+            p95: Duration::from_nanos(number("p95_nanos")?),
+            stddev: Duration::from_nanos(number("stddev_nanos")?),
+            outliers: number("outliers")? as usize,
+        };
+
+        Ok(Record {
+            part,
+            status,
+            answer: optional_string("answer")?,
+            error: optional_string("error")?,
+            stats,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{RECORD_PREFIX, Record};
+    use crate::template::{runner::BenchStats, timings::PartStatus};
+
+    fn get_mock_record() -> Record {
+        Record {
+            part: Some(2),
+            status: PartStatus::Solved,
+            answer: Some("line (1) @ 2\nline 2".into()),
+            error: None,
+            stats: BenchStats::from_durations(&[
+                Duration::from_micros(3),
+                Duration::from_micros(1),
+                Duration::from_micros(2),
+            ]),
+        }
+    }
+
+    #[test]
+    fn roundtrips_records() {
+        let record = get_mock_record();
+        let line = record.to_line();
+        assert!(line.starts_with(RECORD_PREFIX));
+        assert!(!line.contains('\n'));
+        assert_eq!(Record::from_line(&line), Some(Ok(record)));
+    }
+
+    #[test]
+    fn ignores_other_lines() {
+        assert_eq!(Record::from_line("Part 1: 42 (1.0ms)"), None);
+    }
+
+    #[test]
+    fn rejects_malformed_records() {
+        let line = format!("{RECORD_PREFIX}{{\"part\": 1}}");
+        assert!(Record::from_line(&line).unwrap().is_err());
+    }
+}
@@ -1,12 +1,21 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{env, fs, io, time::Duration};
 
-use crate::template::Day;
-use crate::template::timings::Timings;
+use crate::template::timings::{PartStatus, Timings};
+use crate::template::{Day, PuzzleId, Year, last_day};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
+/// Marks the table of `year`. Each year has its own table, the workspace's own event uses
+/// `MARKER`.
+fn marker(year: Option<Year>) -> String {
+    match year {
+        Some(year) => format!("<!--- benchmarking table {year} --->"),
+        None => MARKER.into(),
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
@@ -25,13 +34,8 @@ pub struct TablePosition {
     pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,76 +56,271 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
-
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
-
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+/// Optional parts of the benchmark table, read from the `AOC_README_*` environment variables
+/// that are set in `.cargo/config.toml`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TableConfig {
+    /// Add a column with the number of benchmark samples of each part.
+    pub samples: bool,
+    /// Add a column with the share of each day in the total time.
+    pub share: bool,
+    /// Add a bar chart of each day's time, relative to the slowest day.
+    pub chart: bool,
+    /// Number of parts to list in a section with the slowest parts, none if `0`.
+    pub slowest: usize,
+    /// Add a column that links to the puzzle descriptions of this year.
+    pub links: Option<Year>,
+}
+
+impl TableConfig {
+    /// Links are only added if the year of the table is known, see `PuzzleId::year`.
+    pub fn from_env(year: Option<Year>) -> Self {
+        let flag = |key: &str| env::var(key).is_ok_and(|v| v == "true" || v == "1");
+
+        TableConfig {
+            samples: flag("AOC_README_SAMPLES"),
+            share: flag("AOC_README_SHARE"),
+            chart: flag("AOC_README_CHART"),
+            slowest: env::var("AOC_README_SLOWEST")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0),
+            links: if flag("AOC_README_LINKS") {
+                year.or_else(Year::from_env)
+            } else {
+                None
+            },
+        }
+    }
+}
+
+const CHART_WIDTH: usize = 20;
+
+/// A horizontal bar of `fraction` times `CHART_WIDTH` characters, in steps of an eighth.
+fn bar(fraction: f64) -> String {
+    const PARTIAL: [&str; 8] = ["", "▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    let eighths = (fraction.clamp(0.0, 1.0) * (CHART_WIDTH * 8) as f64).round() as usize;
+    format!("{}{}", "█".repeat(eighths / 8), PARTIAL[eighths % 8])
+}
+
+fn format_samples(samples: Option<u64>) -> String {
+    samples.map_or("-".into(), |s| s.to_string())
+}
+
+fn construct_table(
+    prefix: &str,
+    year: Option<Year>,
+    timings: Timings,
+    total_millis: f64,
+    config: &TableConfig,
+) -> String {
+    let header = match year {
+        Some(year) => format!("{prefix} {year} Benchmarks"),
+        None => format!("{prefix} Benchmarks"),
+    };
+
+    // only show the parse column once a solution uses a separate parser.
+    let has_parse = timings.data.iter().any(|t| t.parse_nanos.is_some());
+
+    let total_nanos: f64 = timings.data.iter().map(|t| t.total_nanos).sum();
+    let slowest_day = timings
+        .data
+        .iter()
+        .map(|t| t.total_nanos)
+        .fold(0_f64, f64::max);
+
+    let mut columns = vec!["Day"];
+    if config.links.is_some() {
+        columns.push("Puzzle");
+    }
+    if has_parse {
+        columns.push("Parse");
+    }
+    columns.extend(["Part 1", "Part 2"]);
+    if config.samples {
+        columns.push("Samples");
+    }
+    if config.share {
+        columns.push("Share");
+    }
+    if config.chart {
+        columns.push("Chart");
+    }
+
+    let mut lines: Vec<String> = vec![marker(year), header, String::new()];
+
+    lines.push(format!("| {} |", columns.join(" | ")));
+    lines.push(format!(
+        "|{} |",
+        " :---: |".repeat(columns.len() - 1) + " :---: "
+    ));
+
+    for timing in &timings.data {
+        let path = PuzzleId {
+            year,
+            day: timing.day,
+        }
+        .bin_path();
+        let mut cells = vec![format!("[Day {}]({})", timing.day.into_inner(), path)];
+
+        if let Some(year) = config.links {
+            cells.push(format!(
+                "[Puzzle](https://adventofcode.com/{year}/day/{})",
+                timing.day.into_inner()
+            ));
+        }
+        if has_parse {
+            cells.push(format!(
+                "`{}`",
+                timing.parse_nanos.map_or("-".into(), format_nanos)
+            ));
+        }
+        cells.push(format_part(timing.part_1_nanos, timing.part_1_status));
+        cells.push(format_part(timing.part_2_nanos, timing.part_2_status));
+        if config.samples {
+            cells.push(format!(
+                "{} / {}",
+                format_samples(timing.part_1_samples),
+                format_samples(timing.part_2_samples)
+            ));
+        }
+        if config.share {
+            cells.push(if total_nanos > 0.0 {
+                format!("{:.1}%", timing.total_nanos / total_nanos * 100.0)
+            } else {
+                "-".into()
+            });
+        }
+        if config.chart {
+            cells.push(if slowest_day > 0.0 {
+                bar(timing.total_nanos / slowest_day)
+            } else {
+                String::new()
+            });
+        }
+
+        lines.push(format!("| {} |", cells.join(" | ")));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+
+    if config.slowest > 0 {
+        let mut parts: Vec<(Day, u8, f64)> = timings
+            .data
+            .iter()
+            .flat_map(|t| {
+                [(1, t.part_1_nanos), (2, t.part_2_nanos)]
+                    .into_iter()
+                    .filter_map(move |(part, nanos)| Some((t.day, part, nanos?)))
+            })
+            .collect();
+        parts.sort_by(|a, b| b.2.total_cmp(&a.2));
+
+        if !parts.is_empty() {
+            lines.push(String::new());
+            lines.push(format!("{prefix}# Slowest parts"));
+            lines.push(String::new());
+            for (i, (day, part, nanos)) in parts.iter().take(config.slowest).enumerate() {
+                lines.push(format!(
+                    "{}. Day {} part {part}: `{}`",
+                    i + 1,
+                    day.into_inner(),
+                    format_nanos(*nanos)
+                ));
+            }
+        }
+    }
+
+    lines.push(marker(year));
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+/// Formats a timing the same way the runner prints it.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos as u64))
+}
+
+fn format_part(nanos: Option<f64>, status: PartStatus) -> String {
+    match nanos {
+        Some(nanos) => format!("`{}`", format_nanos(nanos)),
+        None if status == PartStatus::TimedOut => "timed out".into(),
+        None => "`-`".into(),
+    }
+}
+
+fn update_content(
+    s: &mut String,
+    year: Option<Year>,
+    timings: Timings,
+    total_millis: f64,
+    config: &TableConfig,
+) -> Result<(), Error> {
+    let positions = locate_table(s, &marker(year))?;
+    let table = construct_table("##", year, timings, total_millis, config);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+/// Update the table of `year` in the readme. The table has to be added by hand first, by placing
+/// two of its markers, e.g. `<!--- benchmarking table 2024 --->`, where it should go.
+pub fn update(mut timings: Timings, year: Option<Year>) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    // leave out timings of days that are no longer part of the event, e.g. after a change of
+    // `AOC_MAX_DAY_<year>`.
+    let last_day = last_day(year);
+    timings.data.retain(|t| t.day <= last_day);
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    let config = TableConfig::from_env(year);
+    update_content(&mut readme, year, timings, total_millis, &config)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{MARKER, update_content};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{MARKER, TableConfig, update_content};
+    use crate::template::Year;
+    use crate::{
+        day,
+        template::timings::{PartStatus, Timing, Timings},
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    part_1_nanos: Some(10_000_000_f64),
+                    part_2_nanos: Some(20_000_000_f64),
+                    part_1_status: PartStatus::Solved,
+                    part_2_status: PartStatus::Solved,
                     total_nanos: 3e+10,
+                    ..Timing::new(day!(1))
                 },
                 Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    part_1_nanos: Some(30_000_000_f64),
+                    part_2_nanos: Some(40_000_000_f64),
+                    part_1_status: PartStatus::Solved,
+                    part_2_status: PartStatus::Solved,
                     total_nanos: 7e+10,
+                    ..Timing::new(day!(2))
                 },
                 Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
+                    part_1_nanos: Some(40_000_000_f64),
+                    part_2_nanos: Some(50_000_000_f64),
+                    part_1_status: PartStatus::Solved,
+                    part_2_status: PartStatus::Solved,
                     total_nanos: 9e+10,
+                    ..Timing::new(day!(4))
                 },
             ],
         }
@@ -131,28 +330,63 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            None,
+            get_mock_timings(),
+            190.0,
+            &TableConfig::default(),
+        )
+        .unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            None,
+            get_mock_timings(),
+            190.0,
+            &TableConfig::default(),
+        )
+        .unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            None,
+            get_mock_timings(),
+            190.0,
+            &TableConfig::default(),
+        )
+        .unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            None,
+            get_mock_timings(),
+            190.0,
+            &TableConfig::default(),
+        )
+        .unwrap();
+        update_content(
+            &mut s,
+            None,
+            get_mock_timings(),
+            190.0,
+            &TableConfig::default(),
+        )
+        .unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +394,14 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            None,
+            get_mock_timings(),
+            190.0,
+            &TableConfig::default(),
+        )
+        .unwrap();
         let expected = [
             "foo",
             "bar",
@@ -169,9 +410,9 @@ mod tests {
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/04.rs) | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +421,65 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_parse() {
+        let mut timings = get_mock_timings();
+        timings.data[1].parse_nanos = Some(5_000_000_f64);
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, None, timings, 190.0, &TableConfig::default()).unwrap();
+        assert!(s.contains("| Day | Parse | Part 1 | Part 2 |"));
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `-` | `10.0ms` | `20.0ms` |"));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `5.0ms` | `30.0ms` | `40.0ms` |"));
+    }
+
+    #[test]
+    fn format_timed_out_parts() {
+        let mut timings = get_mock_timings();
+        timings.data[2].part_2_nanos = None;
+        timings.data[2].part_2_status = PartStatus::TimedOut;
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, None, timings, 190.0, &TableConfig::default()).unwrap();
+        assert!(s.contains("| [Day 4](./src/bin/04.rs) | `40.0ms` | timed out |"));
+    }
+
+    #[test]
+    fn format_benchmarks_with_options() {
+        let mut timings = get_mock_timings();
+        for timing in &mut timings.data {
+            timing.part_1_samples = Some(100);
+        }
+        let config = TableConfig {
+            samples: true,
+            share: true,
+            chart: true,
+            slowest: 2,
+            links: Year::new(2025),
+        };
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, None, timings, 190.0, &config).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Puzzle | Part 1 | Part 2 | Samples | Share | Chart |",
+            "| :---: | :---: | :---: | :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | [Puzzle](https://adventofcode.com/2025/day/1) | `10.0ms` | `20.0ms` | 100 / - | 15.8% | ██████▋ |",
+            "| [Day 2](./src/bin/02.rs) | [Puzzle](https://adventofcode.com/2025/day/2) | `30.0ms` | `40.0ms` | 100 / - | 36.8% | ███████████████▌ |",
+            "| [Day 4](./src/bin/04.rs) | [Puzzle](https://adventofcode.com/2025/day/4) | `40.0ms` | `50.0ms` | 100 / - | 47.4% | ████████████████████ |",
+            "",
+            "**Total: 190.00ms**",
+            "",
+            "### Slowest parts",
+            "",
+            "1. Day 4 part 2: `50.0ms`",
+            "2. Day 2 part 2: `40.0ms`",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
 }
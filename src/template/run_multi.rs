@@ -1,44 +1,119 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    io,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Duration,
+};
 
-use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
+use crate::template::{
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day, PuzzleId, Year,
+    bins::{Launcher, Profile},
+    protocol::Record,
+};
 
 use super::{
     all_days,
-    timings::{Timing, Timings},
+    timings::{PartStatus, Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+/// Resource limits for a single day.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// Wall-clock time after which the solution is killed.
+    pub timeout: Option<Duration>,
+    /// Cap on the address space of the solution in megabytes.
+    pub memory_mb: Option<u64>,
+}
 
-    let mut need_space = false;
+impl Limits {
+    pub fn is_limited(&self) -> bool {
+        self.timeout.is_some() || self.memory_mb.is_some()
+    }
+}
 
+/// Run the solutions of `days_to_run` in `year` and print their output ordered by day.
+///
+/// With `build_once`, all bins are built with a single `cargo build` and then executed directly.
+/// With `jobs > 1`, the bins are always built once and the days then run concurrently, their
+/// output being buffered until it is their turn. Timed runs are always sequential so that the days
+/// do not compete for the CPU.
+/// Limits also imply building once, so that compilation does not count against them.
+pub fn run_multi(
+    year: Option<Year>,
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    jobs: usize,
+    build_once: bool,
+    limits: Limits,
+) -> Option<Timings> {
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
+    let days: Vec<Day> = all_days(year)
         .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+        .collect();
+    let mut timings: Vec<Timing> = Vec::with_capacity(days.len());
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+    let jobs = if is_timed { 1 } else { jobs.max(1) };
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    let profile = if is_release {
+        Profile::Release
+    } else {
+        Profile::Debug
+    };
+    let launcher = if build_once || jobs > 1 || limits.is_limited() {
+        Launcher::built(profile, None)
+    } else {
+        Launcher::Cargo(profile)
+    };
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
-        });
+    if jobs == 1 {
+        for (i, &day) in days.iter().enumerate() {
+            print_header(i, day);
+            let puzzle = PuzzleId { year, day };
+            let run =
+                child_commands::run_solution(puzzle, &launcher, is_timed, false, limits).unwrap();
+            timings.extend(collect_timing(day, &run));
+        }
+    } else {
+        run_parallel(
+            &days,
+            jobs,
+            |day| {
+                let puzzle = PuzzleId { year, day };
+                child_commands::run_solution(puzzle, &launcher, is_timed, true, limits).unwrap()
+            },
+            |i, day, run| {
+                print_header(i, day);
+                run.replay();
+                timings.extend(collect_timing(day, &run));
+            },
+        );
+    }
+
+    let count_status = |status: PartStatus| {
+        timings
+            .iter()
+            .flat_map(|t| [t.part_1_status, t.part_2_status])
+            .filter(|s| *s == status)
+            .count()
+    };
+    println!(
+        "\n{ANSI_BOLD}Parts:{ANSI_RESET} {} solved, {} unsolved, {} failed, {} timed out",
+        count_status(PartStatus::Solved),
+        count_status(PartStatus::Unsolved),
+        count_status(PartStatus::Error),
+        count_status(PartStatus::TimedOut)
+    );
 
     if is_timed {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
-            "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+            "{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
         Some(timings)
     } else {
@@ -46,6 +121,111 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
     }
 }
 
+fn print_header(index: usize, day: Day) {
+    if index > 0 {
+        println!();
+    }
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+}
+
+/// The timing of a finished run, `None` if the day is not solved at all.
+fn collect_timing(day: Day, run: &SolutionRun) -> Option<Timing> {
+    if run.records.is_empty() && run.success {
+        println!("Not solved.");
+        return None;
+    }
+
+    let mut timing = child_commands::timing_from_records(&run.records, day);
+    if !run.success {
+        // the binary was killed, crashed or did not compile, blame every part without an answer.
+        let blame = if run.timed_out {
+            PartStatus::TimedOut
+        } else {
+            PartStatus::Error
+        };
+        for status in [&mut timing.part_1_status, &mut timing.part_2_status] {
+            if *status != PartStatus::Solved {
+                *status = blame;
+            }
+        }
+    }
+    Some(timing)
+}
+
+/// Calls `run` for every day on `jobs` threads and passes the results to `report` in the order of
+/// `days`, as soon as all previous days have been reported.
+fn run_parallel<T: Send>(
+    days: &[Day],
+    jobs: usize,
+    run: impl Fn(Day) -> T + Sync,
+    mut report: impl FnMut(usize, Day, T),
+) {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let (sender, next, run) = (sender.clone(), &next, &run);
+            scope.spawn(move || {
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(&day) = days.get(index) else {
+                        break;
+                    };
+                    if sender.send((index, run(day))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut next_report = 0;
+        for (index, result) in receiver {
+            pending.insert(index, result);
+            while let Some(result) = pending.remove(&next_report) {
+                report(next_report, days[next_report], result);
+                next_report += 1;
+            }
+        }
+    });
+}
+
+/// A line the solution wrote to stdout or stderr.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OutputLine {
+    Stdout(String),
+    Stderr(String),
+}
+
+/// Outcome of running the bin of a single day.
+#[derive(Clone, Debug, Default)]
+pub struct SolutionRun {
+    /// The result records reported by the child.
+    pub records: Vec<Record>,
+    /// Whether the child exited successfully.
+    pub success: bool,
+    /// Whether the child was killed because it exceeded the timeout.
+    pub timed_out: bool,
+    /// Human-readable output of the child, if it was buffered instead of forwarded directly.
+    pub output: Vec<OutputLine>,
+}
+
+impl SolutionRun {
+    /// Forward buffered output to stdout/stderr.
+    pub fn replay(&self) {
+        for line in &self.output {
+            match line {
+                OutputLine::Stdout(line) => println!("{line}"),
+                OutputLine::Stderr(line) => eprintln!("{line}"),
+            }
+        }
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
@@ -59,48 +239,74 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting the result records they report.
 pub mod child_commands {
-    use super::{Error, get_path_for_bin};
-    use crate::template::Day;
+    use super::{Error, Limits, OutputLine, SolutionRun};
+    use crate::template::{
+        Day, PuzzleId,
+        bins::{self, Launcher},
+        protocol::{self, Record},
+        runner::BenchStats,
+        timings::{PartStats, PartStatus},
+    };
     use std::{
         io::{BufRead, BufReader},
         path::Path,
-        process::{Command, Stdio},
+        process::{Child, ExitStatus, Stdio},
+        sync::{Arc, Mutex},
         thread,
+        time::{Duration, Instant},
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin of a given puzzle.
+    /// With `buffered`, the output of the child is collected in the result instead of being
+    /// forwarded to stdout/stderr right away. The child is killed once it exceeds `limits.timeout`.
+    pub fn run_solution(
+        puzzle: PuzzleId,
+        launcher: &Launcher,
+        is_timed: bool,
+        buffered: bool,
+        limits: Limits,
+    ) -> Result<SolutionRun, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if !Path::new(&puzzle.bin_path()).exists() {
+            return Ok(SolutionRun {
+                success: true,
+                ..SolutionRun::default()
+            });
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
-
-        if is_release {
-            args.push("--release");
-        }
+        // mirror `--time` flag to child invocations.
+        let args: &[&str] = if is_timed { &["--time"] } else { &[] };
+
+        let mut command = match launcher.command(puzzle, args) {
+            Ok(cmd) => cmd,
+            Err(build_error) => {
+                // the bin did not compile, report it for this day only.
+                let output = if buffered {
+                    vec![OutputLine::Stderr(build_error)]
+                } else {
+                    eprintln!("{build_error}");
+                    vec![]
+                };
+                return Ok(SolutionRun {
+                    success: false,
+                    output,
+                    ..SolutionRun::default()
+                });
+            }
+        };
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        if let Some(megabytes) = limits.memory_mb {
+            command = bins::with_memory_limit(&command, megabytes);
         }
 
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward (or buffer) output while collecting result records from stdout.
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
+        let mut cmd = command
+            .env(protocol::RESULT_FORMAT_ENV, protocol::JSON_LINES)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
@@ -108,85 +314,147 @@ pub mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        // killing the child closes its pipes, which ends the loops below.
+        let watchdog = thread::spawn(move || wait_with_timeout(cmd, limits.timeout));
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+        let output = Arc::new(Mutex::new(vec![]));
+        let forward = move |output: &Mutex<Vec<OutputLine>>, line: OutputLine| {
+            if buffered {
+                output.lock().unwrap().push(line);
+            } else {
+                match line {
+                    OutputLine::Stdout(line) => println!("{line}"),
+                    OutputLine::Stderr(line) => eprintln!("{line}"),
+                }
+            }
+        };
+
+        let thread = {
+            let output = Arc::clone(&output);
+            thread::spawn(move || {
+                stderr.lines().for_each(|line| {
+                    forward(&output, OutputLine::Stderr(line.unwrap()));
+                });
+            })
+        };
+
+        let mut records = vec![];
 
         for line in stdout.lines() {
             let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+            match Record::from_line(&line) {
+                Some(Ok(record)) => records.push(record),
+                Some(Err(e)) => forward(
+                    &output,
+                    OutputLine::Stderr(format!("Could not parse result record: {e}")),
+                ),
+                None => forward(&output, OutputLine::Stdout(line)),
+            }
         }
 
         thread.join().unwrap();
-        cmd.wait()?;
+        let (status, timed_out) = watchdog.join().unwrap()?;
+
+        if timed_out {
+            let timeout = limits.timeout.unwrap_or_default();
+            forward(
+                &output,
+                OutputLine::Stderr(format!("Timed out after {timeout:.1?}.")),
+            );
+        } else if let Some(megabytes) = limits.memory_mb.filter(|_| !status.success()) {
+            forward(
+                &output,
+                OutputLine::Stderr(format!(
+                    "Exited with {status}, possibly due to the memory limit of {megabytes}MB."
+                )),
+            );
+        }
 
-        Ok(output)
+        let output = std::mem::take(&mut *output.lock().unwrap());
+        Ok(SolutionRun {
+            records,
+            success: status.success() && !timed_out,
+            timed_out,
+            output,
+        })
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
+    /// Wait for `child` to exit, killing it once `timeout` has passed.
+    /// Returns its exit status and whether it was killed.
+    fn wait_with_timeout(
+        mut child: Child,
+        timeout: Option<Duration>,
+    ) -> Result<(ExitStatus, bool), Error> {
+        let Some(timeout) = timeout else {
+            return Ok((child.wait()?, false));
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(status) = child.try_wait()? {
+                return Ok((status, false));
+            }
+            if Instant::now() >= deadline {
+                child.kill()?;
+                return Ok((child.wait()?, true));
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+    }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+    pub fn timing_from_records(records: &[Record], day: Day) -> super::Timing {
+        let mut timings = super::Timing::new(day);
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+        for record in records {
+            #[allow(clippy::cast_precision_loss)]
+            let nanos = record.stats.median.as_nanos() as f64;
+            let samples = u64::try_from(record.stats.samples).ok();
+
+            match record.part {
+                None => {
+                    timings.parse_nanos = Some(nanos);
                 }
+                Some(1) => {
+                    timings.part_1_status = record.status;
+                    if record.status == PartStatus::Solved {
+                        timings.part_1_nanos = Some(nanos);
+                        timings.part_1_samples = samples;
+                        timings.part_1_stats = part_stats(&record.stats);
+                    }
+                }
+                Some(2) => {
+                    timings.part_2_status = record.status;
+                    if record.status == PartStatus::Solved {
+                        timings.part_2_nanos = Some(nanos);
+                        timings.part_2_samples = samples;
+                        timings.part_2_stats = part_stats(&record.stats);
+                    }
+                }
+                Some(part) => {
+                    eprintln!("Ignoring result record for unknown part {part}.");
+                    continue;
+                }
+            }
 
+            if record.status == PartStatus::Solved {
                 timings.total_nanos += nanos;
-            });
+            }
+        }
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .next_back()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    /// The spread of a benched part, `None` if it was only run once.
+    fn part_stats(stats: &BenchStats) -> Option<PartStats> {
+        #[allow(clippy::cast_precision_loss)]
+        let nanos = |duration: std::time::Duration| duration.as_nanos() as f64;
+        (stats.samples > 1).then(|| PartStats {
+            min_nanos: nanos(stats.min),
+            p95_nanos: nanos(stats.p95),
+            max_nanos: nanos(stats.max),
+            stddev_nanos: nanos(stats.stddev),
+            outliers: stats.outliers as u64,
+        })
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -205,53 +473,157 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use std::time::Duration;
 
-        use crate::day;
+        use super::timing_from_records;
+
+        use crate::{
+            day,
+            template::{
+                protocol::Record,
+                runner::BenchStats,
+                timings::{PartStats, PartStatus},
+            },
+        };
+
+        fn record(part: Option<u8>, status: PartStatus, samples: &[Duration]) -> Record {
+            Record {
+                part,
+                status,
+                answer: (status == PartStatus::Solved).then(|| "(1) @ 2\n3".into()),
+                error: (status == PartStatus::Error).then(|| "could not parse line 3".into()),
+                stats: BenchStats::from_durations(samples),
+            }
+        }
 
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
+        fn collects_execution_times() {
+            let res = timing_from_records(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    record(Some(1), PartStatus::Solved, &[Duration::from_nanos(74)]),
+                    record(Some(2), PartStatus::Solved, &[Duration::from_millis(74)]),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.total_nanos, 74000074_f64);
+            assert_eq!(res.part_1_nanos, Some(74_f64));
+            assert_eq!(res.part_2_nanos, Some(74_000_000_f64));
+            assert_eq!(res.part_1_status, PartStatus::Solved);
+            assert_eq!(res.part_2_status, PartStatus::Solved);
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
+        fn collects_missing_parts() {
+            let res = timing_from_records(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    record(Some(1), PartStatus::Unsolved, &[Duration::from_millis(1)]),
+                    record(Some(2), PartStatus::Unsolved, &[Duration::from_millis(1)]),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_approx_eq!(res.total_nanos, 0_f64);
+            assert_eq!(res.part_1_nanos.is_none(), true);
+            assert_eq!(res.part_2_nanos.is_none(), true);
         }
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
+        fn collects_parse_times() {
+            let res = timing_from_records(
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
+                    record(None, PartStatus::Solved, &[Duration::from_millis(1)]),
+                    record(Some(1), PartStatus::Solved, &[Duration::from_nanos(74)]),
+                    record(Some(2), PartStatus::Solved, &[Duration::from_millis(1)]),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+            assert_approx_eq!(res.total_nanos, 2000074_f64);
+            assert_eq!(res.parse_nanos, Some(1_000_000_f64));
+            assert_eq!(res.part_1_nanos, Some(74_f64));
+            assert_eq!(res.part_2_nanos, Some(1_000_000_f64));
+        }
+
+        #[test]
+        fn collects_bench_stats() {
+            let samples = [1, 2, 3].map(Duration::from_millis);
+            let res = timing_from_records(
+                &[
+                    record(Some(1), PartStatus::Solved, &samples),
+                    record(Some(2), PartStatus::Solved, &[Duration::from_millis(100)]),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 102000000_f64);
+            assert_eq!(res.part_1_nanos, Some(2_000_000_f64));
+            assert_eq!(res.part_1_samples, Some(3));
+            assert_eq!(res.part_2_samples, Some(1));
+            assert_eq!(
+                res.part_1_stats.unwrap(),
+                PartStats {
+                    min_nanos: 1_000_000_f64,
+                    p95_nanos: 3_000_000_f64,
+                    max_nanos: 3_000_000_f64,
+                    stddev_nanos: 816_497_f64,
+                    outliers: 0,
+                }
+            );
+            assert_eq!(res.part_2_stats, None);
         }
+
+        #[test]
+        fn collects_part_statuses() {
+            let res = timing_from_records(
+                &[
+                    record(Some(1), PartStatus::Error, &[Duration::from_millis(1)]),
+                    record(Some(2), PartStatus::Unsolved, &[Duration::from_millis(1)]),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.part_1_status, PartStatus::Error);
+            assert_eq!(res.part_2_status, PartStatus::Unsolved);
+
+            let res = timing_from_records(
+                &[record(
+                    Some(1),
+                    PartStatus::Solved,
+                    &[Duration::from_millis(100)],
+                )],
+                day!(1),
+            );
+            assert_eq!(res.part_1_status, PartStatus::Solved);
+            assert_eq!(res.part_2_status, PartStatus::Unsolved);
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use super::run_parallel;
+    use crate::{day, template::Day};
+
+    #[test]
+    fn reports_parallel_runs_in_order() {
+        let days = [day!(1), day!(2), day!(3), day!(4), day!(5)];
+        let mut reported: Vec<(usize, Day, u8)> = vec![];
+
+        run_parallel(
+            &days,
+            3,
+            |day| {
+                // finish later days first.
+                thread::sleep(Duration::from_millis(u64::from(10 - day.into_inner()) * 5));
+                day.into_inner() * 10
+            },
+            |i, day, result| reported.push((i, day, result)),
+        );
+
+        assert_eq!(
+            reported,
+            days.iter()
+                .enumerate()
+                .map(|(i, &day)| (i, day, day.into_inner() * 10))
+                .collect::<Vec<_>>()
+        );
     }
 }
@@ -2,34 +2,115 @@
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{Write, stdout};
-use std::process::Output;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
 use crate::template::ANSI_BOLD;
-use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, aoc_cli};
+use crate::template::answers::Answers;
+use crate::template::protocol::{self, Record};
+use crate::template::submissions::{Submissions, Verdict};
+use crate::template::timings::PartStatus;
+use crate::template::{ANSI_ITALIC, ANSI_RESET, PuzzleId, aoc_client};
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Outcome of running a single part of a solution.
+pub enum PartResult<T> {
+    Solved(T),
+    Unsolved,
+    Failed(String),
+}
+
+/// Return types that solution functions may use: `Option<T>` where `None` means "not solved
+/// yet", or `Result<T, E>` where `Err` is reported as a failure of the part.
+pub trait IntoPartResult {
+    type Answer: Display;
+
+    fn into_part_result(self) -> PartResult<Self::Answer>;
+}
+
+impl<T: Display> IntoPartResult for Option<T> {
+    type Answer = T;
+
+    fn into_part_result(self) -> PartResult<T> {
+        match self {
+            Some(result) => PartResult::Solved(result),
+            None => PartResult::Unsolved,
+        }
+    }
+}
+
+impl<T: Display, E: Display> IntoPartResult for Result<T, E> {
+    type Answer = T;
+
+    fn into_part_result(self) -> PartResult<T> {
+        match self {
+            Ok(result) => PartResult::Solved(result),
+            Err(e) => PartResult::Failed(e.to_string()),
+        }
+    }
+}
+
+pub fn run_part<I: Copy, R: IntoPartResult>(
+    func: impl Fn(I) -> R,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, stats) = run_timed(
+        |input| func(input).into_part_result(),
+        input,
+        |result| print_result(result, &part_str, ""),
+    );
+
+    print_result(&result, &part_str, &format_duration(&stats));
+
+    if protocol::is_enabled() {
+        let (status, answer, error) = match &result {
+            PartResult::Solved(answer) => (PartStatus::Solved, Some(answer.to_string()), None),
+            PartResult::Unsolved => (PartStatus::Unsolved, None, None),
+            PartResult::Failed(message) => (PartStatus::Error, None, Some(message.clone())),
+        };
+        let record = Record {
+            part: Some(part),
+            status,
+            answer,
+            error,
+            stats,
+        };
+        println!("{}", record.to_line());
+    }
+
+    if let PartResult::Solved(result) = result {
+        submit_result(result, puzzle, part);
+    }
+}
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+/// Run the shared input parser of a solution and return its output for the parts.
+pub fn run_parse<I: Copy, T>(func: impl Fn(I) -> T, input: I) -> T {
+    let (result, stats) = run_timed(func, input, |_| print!("Parse:"));
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    print!("\r");
+    println!("Parse:{}", format_duration(&stats));
+
+    if protocol::is_enabled() {
+        let record = Record {
+            part: None,
+            status: PartStatus::Solved,
+            answer: None,
+            error: None,
+            stats,
+        };
+        println!("{}", record.to_line());
     }
+
+    result
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Copy, T>(
-    func: impl Fn(I) -> T,
-    input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+fn run_timed<I: Copy, T>(func: impl Fn(I) -> T, input: I, hook: impl Fn(&T)) -> (T, BenchStats) {
     let timer = Instant::now();
     let result = {
         #[cfg(feature = "dhat-heap")]
@@ -41,16 +122,16 @@ fn run_timed<I: Copy, T>(
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let stats = if std::env::args().any(|x| x == "--time") {
         bench(func, input, &base_time)
     } else {
-        (base_time, 1)
+        BenchStats::from_durations(&[base_time])
     };
 
-    (result, run.0, run.1)
+    (result, stats)
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> BenchStats {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
@@ -59,6 +140,11 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
     let bench_iterations =
         (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
+    // let caches, branch predictors and the allocator settle before measuring.
+    for _ in 0..(bench_iterations / 10).max(1) {
+        black_box(func(black_box(input)));
+    }
+
     let mut timers: Vec<Duration> = vec![];
 
     for _ in 0..bench_iterations {
@@ -67,34 +153,109 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    BenchStats::from_durations(&timers)
+}
+
+/// Summary of the per-iteration durations of a benchmark.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BenchStats {
+    pub samples: u128,
+    pub median: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+    /// Samples with a modified z-score above 3.5, i.e. far off the median by the median absolute
+    /// deviation. These are usually caused by the scheduler rather than by the solution.
+    pub outliers: usize,
+}
+
+impl BenchStats {
+    /// Panics if `durations` is empty.
+    pub fn from_durations(durations: &[Duration]) -> Self {
+        let mut nanos: Vec<u128> = durations.iter().map(Duration::as_nanos).collect();
+        nanos.sort_unstable();
+
+        let median = median_of_sorted(&nanos);
+
+        let mut deviations: Vec<u128> = nanos.iter().map(|&n| n.abs_diff(median)).collect();
+        deviations.sort_unstable();
+        let mad = median_of_sorted(&deviations);
+        // 0.6745 * deviation / mad > 3.5, scaled to integers.
+        let outliers = if mad == 0 {
+            0
+        } else {
+            deviations
+                .iter()
+                .filter(|&&d| d * 6745 > mad * 35000)
+                .count()
+        };
+
+        #[allow(clippy::cast_precision_loss)]
+        let stddev = {
+            let count = nanos.len() as f64;
+            let mean = nanos.iter().sum::<u128>() as f64 / count;
+            let variance = nanos
+                .iter()
+                .map(|&n| (n as f64 - mean).powi(2))
+                .sum::<f64>()
+                / count;
+            Duration::from_secs_f64(variance.sqrt() / 1e9)
+        };
+
+        // nearest-rank percentile.
+        let p95_index = (nanos.len() * 95).div_ceil(100).saturating_sub(1);
+
+        Self {
+            samples: nanos.len() as u128,
+            median: duration_from_nanos(median),
+            min: duration_from_nanos(nanos[0]),
+            max: duration_from_nanos(nanos[nanos.len() - 1]),
+            p95: duration_from_nanos(nanos[p95_index]),
+            stddev,
+            outliers,
+        }
+    }
+}
+
+fn median_of_sorted(numbers: &[u128]) -> u128 {
+    let mid = numbers.len() / 2;
+    if numbers.len().is_multiple_of(2) {
+        u128::midpoint(numbers[mid - 1], numbers[mid])
+    } else {
+        numbers[mid]
+    }
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+fn duration_from_nanos(nanos: u128) -> Duration {
+    Duration::from_nanos(u64::try_from(nanos).unwrap_or(u64::MAX))
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+fn format_duration(stats: &BenchStats) -> String {
+    let BenchStats {
+        samples,
+        median,
+        min,
+        max,
+        p95,
+        stddev,
+        outliers,
+    } = stats;
+
+    if *samples == 1 {
+        format!(" ({median:.1?})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(
+            " ({median:.1?} @ {samples} samples; min {min:.1?}, p95 {p95:.1?}, max {max:.1?}, σ {stddev:.1?}, {outliers} outliers)"
+        )
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result<T: Display>(result: &PartResult<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
+        PartResult::Solved(result) => {
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
@@ -114,7 +275,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        PartResult::Unsolved => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -122,17 +283,26 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        PartResult::Failed(message) => {
+            if is_intermediate_result {
+                print!("{part}: ⚠ {message}");
+            } else {
+                print!("\r");
+                println!("{part}: ⚠ {message}             ");
+            }
+        }
     }
 }
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  2. a session token is configured, see `AocClient::from_env`.
+///  3. the submission log does not rule out the answer, unless `--force` is passed.
 fn submit_result<T: Display>(
     result: T,
-    day: Day,
+    puzzle: PuzzleId,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<String, aoc_client::AocClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -155,13 +325,101 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!(
-            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+    let result = result.to_string();
+    let force = args.contains(&"--force".into());
+    let log = Submissions::path(puzzle.year);
+    let mut submissions = match Submissions::before_submit(&log, puzzle.day, part, &result, force) {
+        Ok(submissions) => submissions,
+        Err(reason) => {
+            eprintln!("Not submitting: {reason}");
+            return None;
+        }
+    };
+
+    println!("Submitting result...");
+    let output = aoc_client::submit(puzzle, part, &result);
+
+    match &output {
+        Ok(response) => {
+            let verdict = Verdict::from_response(response);
+
+            submissions.record(puzzle.day, part, &result, verdict);
+            if let Err(e) = submissions.store_file(puzzle.year) {
+                eprintln!("Failed to record submission: {e}");
+            }
+
+            if verdict == Verdict::Correct {
+                record_answer(puzzle, part, &result);
+            }
+        }
+        Err(e) => eprintln!("Failed to submit: {e}"),
+    }
+
+    Some(output)
+}
+
+/// Store an accepted answer so that `cargo verify` can detect regressions later on.
+fn record_answer(puzzle: PuzzleId, part: u8, result: &str) {
+    let mut answers = match Answers::read_from_file(puzzle.year) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("Failed to record answer, the stored answers can't be read: {e}");
+            return;
+        }
+    };
+    answers.set(puzzle.day, part, result);
+
+    match answers.store_file(puzzle.year) {
+        Ok(()) => println!("🎄 Recorded answer for day {}, part {part}.", puzzle.day),
+        Err(e) => eprintln!("Failed to record answer: {e}"),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{BenchStats, format_duration};
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&v| Duration::from_millis(v)).collect()
+    }
+
+    #[test]
+    fn computes_bench_stats() {
+        let stats = BenchStats::from_durations(&millis(&[4, 2, 3, 5, 1, 3, 2, 4, 3, 100]));
+        assert_eq!(stats.samples, 10);
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.max, Duration::from_millis(100));
+        assert_eq!(stats.p95, Duration::from_millis(100));
+        assert_eq!(stats.outliers, 1);
+        assert!(
+            stats.stddev > Duration::from_millis(29) && stats.stddev < Duration::from_millis(30)
         );
-        process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn computes_median_of_even_samples() {
+        let stats = BenchStats::from_durations(&millis(&[1, 2, 4, 5]));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.p95, Duration::from_millis(5));
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn formats_single_sample() {
+        let stats = BenchStats::from_durations(&millis(&[7]));
+        assert_eq!(stats.outliers, 0);
+        assert_eq!(format_duration(&stats), " (7.0ms)");
+    }
+
+    #[test]
+    fn formats_bench_stats() {
+        let stats = BenchStats::from_durations(&millis(&[1, 2, 3]));
+        assert_eq!(
+            format_duration(&stats),
+            " (2.0ms @ 3 samples; min 1.0ms, p95 3.0ms, max 3.0ms, σ 816.5µs, 0 outliers)"
+        );
+    }
 }
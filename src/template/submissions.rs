@@ -0,0 +1,406 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, Year, data_dir};
+
+static SUBMISSIONS_FILE_NAME: &str = "submissions.json";
+
+/// How the puzzle site responded to a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint in which direction.
+    Incorrect,
+    /// Rate limited, the answer was not checked.
+    Wait,
+    /// The response could not be interpreted, e.g. because the part was already solved.
+    Unknown,
+}
+
+impl Verdict {
+    /// Interpret the response of the puzzle site to a submitted answer.
+    pub fn from_response(response: &str) -> Self {
+        if response.contains("That's the right answer") {
+            Verdict::Correct
+        } else if response.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if response.contains("your answer is too low") {
+            Verdict::TooLow
+        } else if response.contains("That's not the right answer") {
+            Verdict::Incorrect
+        } else if response.contains("You gave an answer too recently") {
+            Verdict::Wait
+        } else {
+            Verdict::Unknown
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::Incorrect => "incorrect",
+            Verdict::Wait => "wait",
+            Verdict::Unknown => "unknown",
+        }
+    }
+
+    fn is_wrong(self) -> bool {
+        matches!(
+            self,
+            Verdict::TooHigh | Verdict::TooLow | Verdict::Incorrect
+        )
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "too_high" => Ok(Verdict::TooHigh),
+            "too_low" => Ok(Verdict::TooLow),
+            "incorrect" => Ok(Verdict::Incorrect),
+            "wait" => Ok(Verdict::Wait),
+            "unknown" => Ok(Verdict::Unknown),
+            _ => Err(format!("unknown verdict `{s}`.")),
+        }
+    }
+}
+
+/// A single answer that was sent to the puzzle site.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub verdict: Verdict,
+}
+
+/// Log of all submissions, used to refuse answers that are known to be wrong.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Dehydrate submissions to a JSON file.
+    pub fn store_file(&self, year: Option<Year>) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(Self::path(year))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate submissions from a JSON file. If not present, returns an empty log.
+    /// Fails if the file can't be read or parsed, so that it isn't replaced by a log of only the
+    /// next submission.
+    pub fn read_from_file(year: Option<Year>) -> Result<Self, String> {
+        Self::read_from_path(&Self::path(year))
+    }
+
+    pub fn path(year: Option<Year>) -> PathBuf {
+        data_dir(year).join(SUBMISSIONS_FILE_NAME)
+    }
+
+    fn read_from_path(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(json) => Submissions::try_from(json),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Submissions::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Read the log at `path` to record a submission of `answer` in. Fails if the log can't be
+    /// read, even with `force`, or if it rules out `answer` and `force` is not set.
+    pub fn before_submit(
+        path: &Path,
+        day: Day,
+        part: u8,
+        answer: &str,
+        force: bool,
+    ) -> Result<Self, String> {
+        let submissions = Self::read_from_path(path)
+            .map_err(|e| format!("the submission log can't be read: {e}"))?;
+        if !force {
+            submissions
+                .check(day, part, answer)
+                .map_err(|reason| format!("{reason} Pass --force to submit anyway."))?;
+        }
+        Ok(submissions)
+    }
+
+    /// Append a submission made just now.
+    pub fn record(&mut self, day: Day, part: u8, answer: &str, verdict: Verdict) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        self.data.push(Submission {
+            day,
+            part,
+            answer: answer.into(),
+            timestamp,
+            verdict,
+        });
+    }
+
+    /// Returns why `answer` should not be submitted, if the log already rules it out.
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> Result<(), String> {
+        let previous = self.data.iter().filter(|s| s.day == day && s.part == part);
+
+        let mut lowest_too_high: Option<i128> = None;
+        let mut highest_too_low: Option<i128> = None;
+
+        for submission in previous {
+            if submission.answer == answer {
+                if submission.verdict == Verdict::Correct {
+                    return Err(format!("`{answer}` was already accepted."));
+                }
+                if submission.verdict.is_wrong() {
+                    return Err(format!("`{answer}` is known to be wrong."));
+                }
+            }
+
+            let Ok(value) = submission.answer.parse::<i128>() else {
+                continue;
+            };
+            match submission.verdict {
+                Verdict::TooHigh => {
+                    lowest_too_high = Some(lowest_too_high.map_or(value, |v| v.min(value)));
+                }
+                Verdict::TooLow => {
+                    highest_too_low = Some(highest_too_low.map_or(value, |v| v.max(value)));
+                }
+                _ => {}
+            }
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            if let Some(bound) = lowest_too_high.filter(|&bound| value >= bound) {
+                return Err(format!(
+                    "`{answer}` is not lower than `{bound}`, which is too high."
+                ));
+            }
+            if let Some(bound) = highest_too_low.filter(|&bound| value <= bound) {
+                return Err(format!(
+                    "`{answer}` is not higher than `{bound}`, which is too low."
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Submissions> for JsonValue {
+    fn from(value: Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.as_str().into()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|&x| x as u64)
+                .ok_or(format!("Expected submission.{key} to be a number."))
+        };
+
+        let part =
+            u8::try_from(number("part")?).map_err(|_| "Expected submission.part to be 1 or 2.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .cloned()
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.verdict to be a string.".to_string())
+            .and_then(|s| Verdict::from_str(s))?;
+
+        Ok(Submission {
+            day,
+            part,
+            answer,
+            timestamp: number("timestamp")?,
+            verdict,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Submissions, Verdict};
+    use crate::day;
+
+    fn get_mock_submissions() -> Submissions {
+        let mut submissions = Submissions::default();
+        submissions.record(day!(1), 1, "500", Verdict::TooHigh);
+        submissions.record(day!(1), 1, "100", Verdict::TooLow);
+        submissions.record(day!(1), 1, "300", Verdict::Incorrect);
+        submissions.record(day!(1), 1, "abc", Verdict::Incorrect);
+        submissions.record(day!(1), 2, "7", Verdict::Wait);
+        submissions.record(day!(2), 1, "42", Verdict::Correct);
+        submissions
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        assert_eq!(
+            Verdict::from_response("That's the right answer! You are one gold star closer."),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer; your answer is too high."),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer; your answer is too low."),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer. If you're stuck, ..."),
+            Verdict::Incorrect
+        );
+        assert_eq!(
+            Verdict::from_response("You gave an answer too recently; you have 39s left to wait."),
+            Verdict::Wait
+        );
+        assert_eq!(
+            Verdict::from_response("You don't seem to be solving the right level."),
+            Verdict::Unknown
+        );
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let submissions = get_mock_submissions();
+        assert!(submissions.check(day!(1), 1, "300").is_err());
+        assert!(submissions.check(day!(1), 1, "abc").is_err());
+        assert!(submissions.check(day!(2), 1, "42").is_err());
+        assert!(submissions.check(day!(1), 2, "7").is_ok());
+        assert!(submissions.check(day!(1), 2, "300").is_ok());
+    }
+
+    #[test]
+    fn refuses_answers_out_of_bounds() {
+        let submissions = get_mock_submissions();
+        assert!(submissions.check(day!(1), 1, "500").is_err());
+        assert!(submissions.check(day!(1), 1, "600").is_err());
+        assert!(submissions.check(day!(1), 1, "100").is_err());
+        assert!(submissions.check(day!(1), 1, "-5").is_err());
+        assert!(submissions.check(day!(1), 1, "250").is_ok());
+        assert!(submissions.check(day!(1), 1, "xyz").is_ok());
+    }
+
+    #[test]
+    fn refuses_to_submit_with_a_corrupt_log() {
+        let path = std::env::temp_dir().join("aoc-corrupt-submissions.json");
+        std::fs::write(&path, "{ \"data\": [{ \"day\": \"01\" ").unwrap();
+        let result = Submissions::before_submit(&path, day!(1), 1, "250", true);
+        std::fs::remove_file(&path).unwrap();
+        assert!(result.is_err());
+
+        let missing = std::env::temp_dir().join("aoc-missing-submissions.json");
+        let submissions = Submissions::before_submit(&missing, day!(1), 1, "250", false).unwrap();
+        assert!(submissions.data.is_empty());
+    }
+
+    #[test]
+    fn roundtrips_submissions() {
+        let submissions = get_mock_submissions();
+        let json = tinyjson::JsonValue::from(submissions.clone())
+            .stringify()
+            .unwrap();
+        let roundtrip = Submissions::try_from(json).unwrap();
+        assert_eq!(roundtrip.data, submissions.data);
+    }
+}
@@ -1,19 +1,102 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{Day, Year, data_dir};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static TIMINGS_FILE_NAME: &str = "timings.json";
+
+/// Version of the `timings.json` schema written by this template.
+/// Files without a version predate numeric timings and are upgraded when read.
+pub const TIMINGS_VERSION: u64 = 2;
+
+/// Whether a part produced an answer, reported that it is not solved yet, failed, or was stopped
+/// because it exceeded the time limit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PartStatus {
+    Solved,
+    #[default]
+    Unsolved,
+    Error,
+    TimedOut,
+}
+
+impl PartStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PartStatus::Solved => "solved",
+            PartStatus::Unsolved => "unsolved",
+            PartStatus::Error => "error",
+            PartStatus::TimedOut => "timed_out",
+        }
+    }
+}
+
+impl FromStr for PartStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "solved" => Ok(PartStatus::Solved),
+            "unsolved" => Ok(PartStatus::Unsolved),
+            "error" => Ok(PartStatus::Error),
+            "timed_out" => Ok(PartStatus::TimedOut),
+            _ => Err(format!("unknown part status `{s}`.")),
+        }
+    }
+}
+
+/// Spread of the benchmark samples of a part, in nanoseconds.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PartStats {
+    pub min_nanos: f64,
+    pub p95_nanos: f64,
+    pub max_nanos: f64,
+    pub stddev_nanos: f64,
+    pub outliers: u64,
+}
 
 /// Represents benchmark times for a single day.
+/// The `_nanos` fields hold the median of the benchmark samples, and the `_samples` fields the
+/// number of samples they were taken from, if known.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub parse_nanos: Option<f64>,
+    pub part_1_nanos: Option<f64>,
+    pub part_2_nanos: Option<f64>,
+    pub part_1_samples: Option<u64>,
+    pub part_2_samples: Option<u64>,
+    pub part_1_status: PartStatus,
+    pub part_2_status: PartStatus,
+    pub part_1_stats: Option<PartStats>,
+    pub part_2_stats: Option<PartStats>,
     pub total_nanos: f64,
 }
 
+impl Timing {
+    /// Timing of a day without any results yet.
+    pub fn new(day: Day) -> Self {
+        Timing {
+            day,
+            parse_nanos: None,
+            part_1_nanos: None,
+            part_2_nanos: None,
+            part_1_samples: None,
+            part_2_samples: None,
+            part_1_status: PartStatus::Unsolved,
+            part_2_status: PartStatus::Unsolved,
+            part_1_stats: None,
+            part_2_stats: None,
+            total_nanos: 0_f64,
+        }
+    }
+}
+
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
@@ -23,18 +106,20 @@ pub struct Timings {
 
 impl Timings {
     /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    pub fn store_file(&self, year: Option<Year>) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(data_dir(year).join(TIMINGS_FILE_NAME))?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from)
-            .unwrap_or_default()
+    /// Fails if the file can't be read or parsed, e.g. because a newer template wrote it.
+    pub fn read_from_file(year: Option<Year>) -> Result<Self, String> {
+        match fs::read_to_string(data_dir(year).join(TIMINGS_FILE_NAME)) {
+            Ok(json) => Timings::try_from(json),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Timings::default()),
+            Err(e) => Err(e.to_string()),
+        }
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
@@ -51,7 +136,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -63,7 +148,22 @@ impl Timings {
     pub fn is_day_complete(&self, day: Day) -> bool {
         self.data
             .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+            .any(|t| t.day == day && t.part_1_nanos.is_some() && t.part_2_nanos.is_some())
+    }
+}
+
+/// Nanoseconds of a duration formatted with `{:?}`, e.g. `74.13ms`.
+fn parse_duration_nanos(s: &str) -> Option<f64> {
+    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
+        s.split(postfix).next()?.trim().parse().ok()
+    }
+
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    match s {
+        s if s.contains("ns") => parse_to_float(s, "ns"),
+        s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
     }
 }
 
@@ -73,6 +173,8 @@ impl From<Timings> for JsonValue {
     fn from(value: Timings) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("version".into(), JsonValue::Number(TIMINGS_VERSION as f64));
         map.insert(
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
@@ -88,9 +190,26 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        // files written before the schema was versioned are version 1.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let version = match json.get("version") {
+            Some(v) => v
+                .get::<f64>()
+                .map(|&x| x as u64)
+                .ok_or("expected `json.version` to be a number.")?,
+            None => 1,
+        };
+        if version > TIMINGS_VERSION {
+            return Err(format!(
+                "timings file has version {version}, but only versions up to {TIMINGS_VERSION} are supported."
+            ));
+        }
+
+        let json_data = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
@@ -114,25 +233,47 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
+        for (key, nanos) in [
+            ("parse_nanos", value.parse_nanos),
+            ("part_1_nanos", value.part_1_nanos),
+            ("part_2_nanos", value.part_2_nanos),
+        ] {
+            map.insert(key.into(), nanos.map_or(JsonValue::Null, JsonValue::Number));
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        for (key, samples) in [
+            ("part_1_samples", value.part_1_samples),
+            ("part_2_samples", value.part_2_samples),
+        ] {
+            map.insert(
+                key.into(),
+                samples.map_or(JsonValue::Null, |x| JsonValue::Number(x as f64)),
+            );
+        }
 
         map.insert(
-            "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            "part_1_status".into(),
+            JsonValue::String(value.part_1_status.as_str().into()),
         );
-
         map.insert(
-            "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            "part_2_status".into(),
+            JsonValue::String(value.part_2_status.as_str().into()),
         );
 
+        for (key, stats) in [
+            ("part_1_stats", &value.part_1_stats),
+            ("part_2_stats", &value.part_2_stats),
+        ] {
+            map.insert(
+                key.into(),
+                match stats {
+                    Some(x) => JsonValue::from(x),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -151,25 +292,66 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
-
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
-
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // version 1 files only contain the formatted timings, parse them back into numbers.
+        // solutions without a separate parser have no `parse` timing.
+        let nanos = |key: &str| match json.get(&format!("{key}_nanos")) {
+            Some(v) if !v.is_null() => v.get::<f64>().copied().map(Some).ok_or(format!(
+                "Expected timing.{key}_nanos to be null or a number."
+            )),
+            _ => match json.get(key) {
+                Some(v) if !v.is_null() => v
+                    .get::<String>()
+                    .map(|t| parse_duration_nanos(t))
+                    .ok_or(format!("Expected timing.{key} to be null or string.")),
+                _ => Ok(None),
+            },
+        };
+
+        let parse_nanos = nanos("parse")?;
+        let part_1_nanos = nanos("part_1")?;
+        let part_2_nanos = nanos("part_2")?;
+
+        // older timing files do not contain a status, derive it from the presence of a timing.
+        let part_status = |key: &str, timing: Option<f64>| match json.get(key) {
+            Some(v) => v
+                .get::<String>()
+                .ok_or(format!("Expected timing.{key} to be a string."))
+                .and_then(|s| PartStatus::from_str(s)),
+            None if timing.is_some() => Ok(PartStatus::Solved),
+            None => Ok(PartStatus::Unsolved),
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let samples = |key: &str| match json.get(key) {
+            Some(v) if !v.is_null() => v
+                .get::<f64>()
+                .map(|&x| Some(x as u64))
+                .ok_or(format!("Expected timing.{key} to be null or a number.")),
+            _ => Ok(None),
+        };
+
+        // timings without benchmark samples, as well as older timing files, have no stats.
+        let part_stats = |key: &str| match json.get(key) {
+            Some(v) if !v.is_null() => PartStats::try_from(v).map(Some),
+            _ => Ok(None),
+        };
+
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            parse_nanos,
+            part_1_nanos,
+            part_2_nanos,
+            part_1_samples: samples("part_1_samples")?,
+            part_2_samples: samples("part_2_samples")?,
+            part_1_status: part_status("part_1_status", part_1_nanos)?,
+            part_2_status: part_status("part_2_status", part_2_nanos)?,
+            part_1_stats: part_stats("part_1_stats")?,
+            part_2_stats: part_stats("part_2_stats")?,
             total_nanos,
         })
     }
@@ -177,39 +359,95 @@ impl TryFrom<&JsonValue> for Timing {
 
 /* -------------------------------------------------------------------------- */
 
+impl From<&PartStats> for JsonValue {
+    fn from(value: &PartStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("min_nanos".into(), JsonValue::Number(value.min_nanos));
+        map.insert("p95_nanos".into(), JsonValue::Number(value.p95_nanos));
+        map.insert("max_nanos".into(), JsonValue::Number(value.max_nanos));
+        map.insert("stddev_nanos".into(), JsonValue::Number(value.stddev_nanos));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part stats to be a JSON object.")?;
+
+        let nanos = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .copied()
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let outliers = json
+            .get("outliers")
+            .and_then(|v| v.get::<f64>())
+            .map(|&x| x as u64)
+            .ok_or("Expected stats.outliers to be a number.")?;
+
+        Ok(PartStats {
+            min_nanos: nanos("min_nanos")?,
+            p95_nanos: nanos("p95_nanos")?,
+            max_nanos: nanos("max_nanos")?,
+            stddev_nanos: nanos("stddev_nanos")?,
+            outliers,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 #[cfg(feature = "test_lib")]
 mod tests {
     use crate::day;
 
-    use super::{Timing, Timings};
+    use super::{PartStatus, Timing, Timings};
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    part_1_nanos: Some(10_000_000_f64),
+                    part_2_nanos: Some(20_000_000_f64),
+                    part_1_status: PartStatus::Solved,
+                    part_2_status: PartStatus::Solved,
                     total_nanos: 3e+10,
+                    ..Timing::new(day!(1))
                 },
                 Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    part_1_nanos: Some(30_000_000_f64),
+                    part_2_nanos: Some(40_000_000_f64),
+                    part_1_status: PartStatus::Solved,
+                    part_2_status: PartStatus::Solved,
                     total_nanos: 7e+10,
+                    ..Timing::new(day!(2))
                 },
                 Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: None,
+                    part_1_nanos: Some(40_000_000_f64),
+                    part_1_status: PartStatus::Solved,
                     total_nanos: 4e+10,
+                    ..Timing::new(day!(4))
                 },
             ],
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::timings::{PartStatus, Timings},
+        };
 
         #[test]
         fn handles_json_timings() {
@@ -218,11 +456,74 @@ mod tests {
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
-            assert_eq!(timing.part_2, None);
+            assert_eq!(timing.part_1_nanos, Some(1_000_000_f64));
+            assert_eq!(timing.part_2_nanos, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_part_statuses() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": null, "part_2": null, "part_1_status": "error", "total_nanos": 0 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1_status, PartStatus::Error);
+            assert_eq!(timing.part_2_status, PartStatus::Unsolved);
+        }
+
+        #[test]
+        fn handles_part_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "min_nanos": 900000, "p95_nanos": 1200000, "max_nanos": 2000000, "stddev_nanos": 50000, "outliers": 3 }, "part_2_stats": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1_stats.as_ref().unwrap();
+            assert_eq!(stats.p95_nanos, 1_200_000.0);
+            assert_eq!(stats.outliers, 3);
+            assert_eq!(timing.part_2_stats, None);
+
+            let roundtrip = Timings::try_from(
+                tinyjson::JsonValue::from(timings.clone())
+                    .stringify()
+                    .unwrap(),
+            )
+            .unwrap();
+            assert_eq!(roundtrip.data[0].part_1_stats, timing.part_1_stats);
+        }
+
+        #[test]
+        fn upgrades_unversioned_timings() {
+            let json = r#"{ "data": [{ "day": "01", "parse": "2.5µs", "part_1": "74.13ms", "part_2": null, "total_nanos": 74132500 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.parse_nanos, Some(2500.0));
+            assert!((timing.part_1_nanos.unwrap() - 74_130_000.0).abs() < 1.0);
+            assert_eq!(timing.part_2_nanos, None);
+            assert_eq!(timing.part_1_samples, None);
+        }
+
+        #[test]
+        fn handles_numeric_timings() {
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "part_1": "1.0ms", "part_2": null, "part_1_nanos": 1000123, "part_1_samples": 42, "total_nanos": 1000123 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1_nanos, Some(1_000_123.0));
+            assert_eq!(timing.part_1_samples, Some(42));
+
+            let roundtrip = Timings::try_from(
+                tinyjson::JsonValue::from(timings.clone())
+                    .stringify()
+                    .unwrap(),
+            )
+            .unwrap();
+            assert_eq!(roundtrip.data[0].part_1_nanos, Some(1_000_123.0));
+            assert_eq!(roundtrip.data[0].part_1_samples, Some(42));
+        }
+
+        #[test]
+        fn rejects_newer_versions() {
+            let json = r#"{ "version": 99, "data": [] }"#.to_string();
+            assert!(Timings::try_from(json).is_err());
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -245,6 +546,19 @@ mod tests {
         }
     }
 
+    mod parse_duration_nanos {
+        use crate::template::timings::parse_duration_nanos;
+
+        #[test]
+        fn parses_duration_strings() {
+            assert_eq!(parse_duration_nanos("74.13ns"), Some(74.13));
+            assert_eq!(parse_duration_nanos("2.5µs"), Some(2500.0));
+            assert_eq!(parse_duration_nanos("74.13ms"), Some(74_130_000.0));
+            assert_eq!(parse_duration_nanos("2s"), Some(2_000_000_000.0));
+            assert_eq!(parse_duration_nanos("-"), None);
+        }
+    }
+
     mod serialization {
         use super::get_mock_timings;
         use std::collections::HashMap;
@@ -271,49 +585,46 @@ mod tests {
     mod is_day_complete {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::timings::{PartStatus, Timing, Timings},
         };
 
         #[test]
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
+                    part_1_nanos: Some(1_000_000_f64),
+                    part_2_nanos: Some(2_000_000_f64),
+                    part_1_status: PartStatus::Solved,
+                    part_2_status: PartStatus::Solved,
                     total_nanos: 3_000_000_000_f64,
+                    ..Timing::new(day!(1))
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: None,
+                    part_1_nanos: Some(1_000_000_f64),
+                    part_1_status: PartStatus::Solved,
                     total_nanos: 1_000_000_000_f64,
+                    ..Timing::new(day!(1))
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0.0,
-                }],
+                data: vec![Timing::new(day!(1))],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
@@ -329,12 +640,7 @@ mod tests {
         fn handles_disjunct_timings() {
             let timings = get_mock_timings();
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(3),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(3))],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -349,12 +655,7 @@ mod tests {
             let timings = get_mock_timings();
 
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(2),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(2))],
             };
             let merged = timings.merge(&other);
 
@@ -0,0 +1,245 @@
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::template::Day;
+
+/// The year of an advent event, starting with the first one in 2015.
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year = Year::new(2024).unwrap();
+/// assert_eq!(year.to_string(), "2024");
+/// assert!(Year::new(2014).is_none());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if an event took place in it,
+    /// returns [`None`] otherwise.
+    pub const fn new(year: u16) -> Option<Self> {
+        if year < 2015 {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// The year of the workspace's own event, configured with `AOC_YEAR` in `.cargo/config.toml`.
+    pub fn from_env() -> Option<Self> {
+        env::var("AOC_YEAR").ok()?.parse().ok()
+    }
+
+    /// The last day of the event. Events have 25 puzzles up to 2024 and 12 from 2025 on.
+    /// Can be overridden with `AOC_MAX_DAY_<year>`, e.g. `AOC_MAX_DAY_2025 = "12"`. An invalid
+    /// override is reported and ignored.
+    pub fn max_day(self) -> Day {
+        let key = format!("AOC_MAX_DAY_{self}");
+        let Ok(value) = env::var(&key) else {
+            return self.default_max_day();
+        };
+        value.parse().unwrap_or_else(|e| {
+            eprintln!("Ignoring invalid {key} `{value}`: {e}");
+            self.default_max_day()
+        })
+    }
+
+    fn default_max_day(self) -> Day {
+        if self.0 >= 2025 {
+            crate::day!(12)
+        } else {
+            crate::day!(25)
+        }
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year of 2015 or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The year to scope bins and data by, `None` for the workspace's own event.
+pub fn scoped_year(year: Option<Year>) -> Option<Year> {
+    year.filter(|&year| Some(year) != Year::from_env())
+}
+
+/// The last day of `year`, or of the workspace's own event if `None`.
+/// Falls back to the 25th if the year is not known.
+pub fn last_day(year: Option<Year>) -> Day {
+    year.or_else(Year::from_env)
+        .map_or(crate::day!(25), Year::max_day)
+}
+
+/// Directory that holds the data files of `year`.
+/// The workspace's own event (`None`) keeps its files directly in `data`.
+pub fn data_dir(year: Option<Year>) -> PathBuf {
+    match year {
+        Some(year) => PathBuf::from("data").join(year.to_string()),
+        None => PathBuf::from("data"),
+    }
+}
+
+/// Identifies a puzzle across events.
+///
+/// `year` is `None` for the workspace's own event (see [`Year::from_env`]), whose bins and data
+/// live in the flat layout, e.g. `src/bin/05.rs` and `data/inputs/05.txt`. Puzzles of other
+/// years are scoped by year, e.g. `src/bin/2024-05.rs` and `data/2024/inputs/05.txt`.
+///
+/// # Display
+/// This value displays as the name of its bin.
+///
+/// ```
+/// # use advent_of_code::template::{Day, PuzzleId, Year};
+/// let day = Day::new(5).unwrap();
+/// assert_eq!(PuzzleId::from(day).to_string(), "05");
+/// assert_eq!(PuzzleId { year: Year::new(2024), day }.to_string(), "2024-05");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: Option<Year>,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    /// Creates a [`PuzzleId`], treating the year of the workspace's own event like no year.
+    pub fn new(year: Option<Year>, day: Day) -> Self {
+        Self {
+            year: scoped_year(year),
+            day,
+        }
+    }
+
+    /// Derives the year of a solution from the name of its bin, i.e. `2024-05` or `05`.
+    pub const fn from_bin_name(name: &str, day: Day) -> Self {
+        let bytes = name.as_bytes();
+        let mut year = None;
+
+        if bytes.len() > 5 && bytes[4] == b'-' {
+            let mut value: u16 = 0;
+            let mut i = 0;
+            while i < 4 && bytes[i].is_ascii_digit() {
+                value = value * 10 + (bytes[i] - b'0') as u16;
+                i += 1;
+            }
+            if i == 4 {
+                year = Year::new(value);
+            }
+        }
+
+        Self { year, day }
+    }
+
+    /// The year of the event, falling back to `AOC_YEAR` for the workspace's own event.
+    pub fn year(&self) -> Option<Year> {
+        self.year.or_else(Year::from_env)
+    }
+
+    pub fn bin_path(&self) -> String {
+        format!("./src/bin/{self}.rs")
+    }
+
+    /// Path of the file of this puzzle in a data folder, e.g. `data/2024/inputs/05.txt`.
+    pub fn data_path(&self, folder: &str, extension: &str) -> PathBuf {
+        data_dir(self.year)
+            .join(folder)
+            .join(format!("{}.{extension}", self.day))
+    }
+}
+
+impl From<Day> for PuzzleId {
+    fn from(day: Day) -> Self {
+        Self { year: None, day }
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.year {
+            Some(year) => write!(f, "{year}-{}", self.day),
+            None => write!(f, "{}", self.day),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{PuzzleId, Year, data_dir};
+    use crate::day;
+
+    #[test]
+    fn derives_max_day_from_year() {
+        assert_eq!(Year::new(2015).unwrap().default_max_day(), day!(25));
+        assert_eq!(Year::new(2024).unwrap().default_max_day(), day!(25));
+        assert_eq!(Year::new(2025).unwrap().default_max_day(), day!(12));
+    }
+
+    #[test]
+    fn parses_bin_names() {
+        assert_eq!(
+            PuzzleId::from_bin_name("2024-05", day!(5)),
+            PuzzleId {
+                year: Year::new(2024),
+                day: day!(5)
+            }
+        );
+        assert_eq!(PuzzleId::from_bin_name("05", day!(5)).year, None);
+        assert_eq!(PuzzleId::from_bin_name("20x4-05", day!(5)).year, None);
+        assert_eq!(PuzzleId::from_bin_name("1999-05", day!(5)).year, None);
+    }
+
+    #[test]
+    fn scopes_paths_by_year() {
+        let flat = PuzzleId::from(day!(5));
+        assert_eq!(flat.bin_path(), "./src/bin/05.rs");
+        assert_eq!(
+            flat.data_path("inputs", "txt"),
+            PathBuf::from("data/inputs/05.txt")
+        );
+
+        let scoped = PuzzleId {
+            year: Year::new(2024),
+            day: day!(5),
+        };
+        assert_eq!(scoped.bin_path(), "./src/bin/2024-05.rs");
+        assert_eq!(
+            scoped.data_path("puzzles", "md"),
+            PathBuf::from("data/2024/puzzles/05.md")
+        );
+        assert_eq!(data_dir(scoped.year), PathBuf::from("data/2024"));
+    }
+}
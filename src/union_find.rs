@@ -0,0 +1,258 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Disjoint-set forest over the elements `0..len()` with path compression and union by size.
+#[derive(Debug, Clone, Default)]
+pub struct UnionFind {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+    components: usize,
+}
+
+impl UnionFind {
+    /// Creates `n` singleton components.
+    pub fn new(n: usize) -> Self {
+        Self {
+            parents: (0..n).collect(),
+            sizes: vec![1; n],
+            components: n,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// Adds a new singleton component and returns its element.
+    pub fn add(&mut self) -> usize {
+        let element = self.parents.len();
+        self.parents.push(element);
+        self.sizes.push(1);
+        self.components += 1;
+        element
+    }
+
+    /// Returns the representative of the component containing `element`.
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        let mut current = element;
+        while self.parents[current] != root {
+            let next = self.parents[current];
+            self.parents[current] = root;
+            current = next;
+        }
+
+        root
+    }
+
+    /// Merges the components of `a` and `b`. Returns false if they were already connected.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        if self.sizes[a] < self.sizes[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parents[b] = a;
+        self.sizes[a] += self.sizes[b];
+        self.components -= 1;
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+
+    pub fn component_size(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.sizes[root]
+    }
+
+    /// Sizes of all components, largest first.
+    pub fn component_sizes(&self) -> Vec<usize> {
+        let mut sizes: Vec<usize> = (0..self.len())
+            .filter(|&e| self.parents[e] == e)
+            .map(|e| self.sizes[e])
+            .collect();
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        sizes
+    }
+
+    /// Iterates over the elements of every component.
+    pub fn groups(&mut self) -> impl Iterator<Item = Vec<usize>> {
+        let mut groups: HashMap<usize, Vec<usize>> = HashMap::new();
+        for element in 0..self.len() {
+            let root = self.find(element);
+            groups.entry(root).or_default().push(element);
+        }
+        groups.into_values()
+    }
+}
+
+/// `UnionFind` over arbitrary hashable keys, e.g. points. Keys are added on first use.
+#[derive(Debug, Clone)]
+pub struct KeyedUnionFind<K> {
+    keys: Vec<K>,
+    indices: HashMap<K, usize>,
+    inner: UnionFind,
+}
+
+impl<K: Clone + Eq + Hash> Default for KeyedUnionFind<K> {
+    fn default() -> Self {
+        Self {
+            keys: vec![],
+            indices: HashMap::new(),
+            inner: UnionFind::default(),
+        }
+    }
+}
+
+impl<K: Clone + Eq + Hash> KeyedUnionFind<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.indices.contains_key(key)
+    }
+
+    /// Adds `key` as a singleton component if it is not known yet and returns its index.
+    pub fn insert(&mut self, key: K) -> usize {
+        if let Some(&index) = self.indices.get(&key) {
+            return index;
+        }
+
+        let index = self.inner.add();
+        self.keys.push(key.clone());
+        self.indices.insert(key, index);
+        index
+    }
+
+    /// Returns the representative key of the component containing `key`.
+    pub fn find(&mut self, key: &K) -> Option<&K> {
+        let index = *self.indices.get(key)?;
+        let root = self.inner.find(index);
+        Some(&self.keys[root])
+    }
+
+    /// Merges the components of `a` and `b`. Returns false if they were already connected.
+    pub fn union(&mut self, a: K, b: K) -> bool {
+        let (a, b) = (self.insert(a), self.insert(b));
+        self.inner.union(a, b)
+    }
+
+    pub fn connected(&mut self, a: &K, b: &K) -> bool {
+        match (self.indices.get(a), self.indices.get(b)) {
+            (Some(&a), Some(&b)) => self.inner.connected(a, b),
+            _ => a == b,
+        }
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.inner.component_count()
+    }
+
+    /// Size of the component containing `key`, `None` if the key is unknown.
+    pub fn component_size(&mut self, key: &K) -> Option<usize> {
+        let index = *self.indices.get(key)?;
+        Some(self.inner.component_size(index))
+    }
+
+    /// Sizes of all components, largest first.
+    pub fn component_sizes(&self) -> Vec<usize> {
+        self.inner.component_sizes()
+    }
+
+    /// Iterates over the keys of every component.
+    pub fn groups(&mut self) -> impl Iterator<Item = Vec<K>> {
+        let groups: Vec<Vec<usize>> = self.inner.groups().collect();
+        groups
+            .into_iter()
+            .map(|g| g.into_iter().map(|i| self.keys[i].clone()).collect())
+    }
+}
+
+impl<K: Clone + Eq + Hash> FromIterator<K> for KeyedUnionFind<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut union_find = Self::new();
+        for key in iter {
+            union_find.insert(key);
+        }
+        union_find
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra_helpers::Point3;
+
+    #[test]
+    fn test_union_find() {
+        let mut uf = UnionFind::new(6);
+        assert_eq!(uf.component_count(), 6);
+
+        assert!(uf.union(0, 1));
+        assert!(uf.union(1, 2));
+        assert!(!uf.union(2, 0));
+        assert!(uf.union(3, 4));
+
+        assert_eq!(uf.component_count(), 3);
+        assert!(uf.connected(0, 2));
+        assert!(!uf.connected(0, 3));
+        assert_eq!(uf.component_size(2), 3);
+        assert_eq!(uf.component_sizes(), vec![3, 2, 1]);
+
+        let mut groups: Vec<Vec<usize>> = uf.groups().collect();
+        groups.sort();
+        assert_eq!(groups, vec![vec![0, 1, 2], vec![3, 4], vec![5]]);
+
+        let e = uf.add();
+        assert_eq!(e, 6);
+        assert_eq!(uf.component_count(), 4);
+    }
+
+    #[test]
+    fn test_keyed_union_find() {
+        let a = Point3::new(0_isize, 0, 0);
+        let b = Point3::new(1, 2, 3);
+        let c = Point3::new(-1, 5, 2);
+
+        let mut uf: KeyedUnionFind<Point3<isize>> = [a, b, c].into_iter().collect();
+        assert_eq!(uf.component_count(), 3);
+        assert!(uf.union(a, c));
+        assert!(uf.connected(&c, &a));
+        assert!(!uf.connected(&a, &b));
+        assert_eq!(uf.component_size(&a), Some(2));
+        assert_eq!(uf.component_size(&Point3::new(9, 9, 9)), None);
+        let root = uf.find(&a).cloned();
+        assert_eq!(uf.find(&c).cloned(), root);
+
+        assert!(uf.union(b, Point3::new(9, 9, 9)));
+        assert_eq!(uf.len(), 4);
+        assert_eq!(uf.component_sizes(), vec![2, 2]);
+        assert_eq!(uf.groups().count(), 2);
+    }
+}